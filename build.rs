@@ -0,0 +1,57 @@
+//! Generates the table of embedded inputs for the `embedded-inputs` feature.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Matches `dayN.txt` and `dayN_test.txt`.
+fn is_input_file(name: &str) -> bool {
+    let day = match name.strip_prefix("day").and_then(|rest| rest.strip_suffix(".txt")) {
+        Some(rest) => rest.strip_suffix("_test").unwrap_or(rest),
+        None => return false,
+    };
+    !day.is_empty() && day.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Matches the per-year directories, e.g. `2021`.
+fn is_year_dir(name: &str) -> bool {
+    name.len() == 4 && name.chars().all(|c| c.is_ascii_digit())
+}
+
+fn read_dir(dir: &Path) -> impl Iterator<Item = String> {
+    fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("Failed to read {}: {}", dir.display(), err))
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    if env::var_os("CARGO_FEATURE_EMBEDDED_INPUTS").is_none() {
+        return;
+    }
+    let data_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("data");
+    println!("cargo:rerun-if-changed={}", data_dir.display());
+
+    let mut names: Vec<String> = Vec::new();
+    for year in read_dir(&data_dir).filter(|name| is_year_dir(name)) {
+        let year_dir = data_dir.join(&year);
+        println!("cargo:rerun-if-changed={}", year_dir.display());
+        names.extend(
+            read_dir(&year_dir)
+                .filter(|name| is_input_file(name))
+                .map(|name| format!("{}/{}", year, name)),
+        );
+    }
+    names.sort();
+
+    let mut table = String::from("&[\n");
+    for name in names {
+        let path = data_dir.join(&name);
+        println!("cargo:rerun-if-changed={}", path.display());
+        table.push_str(&format!("    ({:?}, include_str!({:?})),\n", name, path.display().to_string()));
+    }
+    table.push_str("]\n");
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("embedded_inputs.rs");
+    fs::write(&out, table).unwrap_or_else(|err| panic!("Failed to write {}: {}", out.display(), err));
+}
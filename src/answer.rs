@@ -0,0 +1,112 @@
+use std::fmt;
+
+use crate::json::Value;
+
+/// Result of running a single task.
+///
+/// Most puzzles are answered with a number, but some (like day 13) produce
+/// text or a picture that has to be read by a human.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    Text(String),
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Grid(rows) if rows.len() > 1)
+    }
+
+    /// Numbers stay numbers, text becomes a string and grids an array of rows.
+    pub fn to_json(&self) -> Value {
+        match self {
+            Answer::Int(value) => Value::from(*value),
+            Answer::Text(value) => Value::from(value.as_str()),
+            Answer::Grid(rows) => Value::Array(rows.iter().map(|r| Value::from(r.as_str())).collect()),
+        }
+    }
+
+    /// Numbers become `Int`, strings `Text` and arrays of strings a `Grid`.
+    pub fn from_json(value: &Value) -> Option<Self> {
+        match value {
+            Value::Int(_) | Value::Number(_) => value.as_i64().map(Answer::Int),
+            Value::String(value) => Some(Answer::Text(value.to_string())),
+            Value::Array(rows) => rows
+                .iter()
+                .map(|row| row.as_str().map(|r| r.to_string()))
+                .collect::<Option<Vec<_>>>()
+                .map(Answer::Grid),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Int(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_int() {
+        assert_eq!("42", Answer::from(42).to_string())
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        for answer in [
+            Answer::Int(3318837563123),
+            Answer::Int(i64::MAX),
+            Answer::from("lkrebprk"),
+            Answer::Grid(vec![String::from("#."), String::from(".#")]),
+        ] {
+            assert_eq!(Some(answer.clone()), Answer::from_json(&answer.to_json()))
+        }
+    }
+
+    #[test]
+    fn test_from_json() {
+        let parse = |raw: &str| Answer::from_json(&crate::json::parse(raw).unwrap());
+        assert_eq!(Some(Answer::Int(3318837563123)), parse("3318837563123"));
+        assert_eq!(Some(Answer::from("lkrebprk")), parse(r#""lkrebprk""#));
+        assert_eq!(
+            Some(Answer::Grid(vec![String::from("#."), String::from(".#")])),
+            parse(r##"["#.", ".#"]"##)
+        );
+        assert_eq!(None, Answer::from_json(&Value::from(1.5)))
+    }
+
+    #[test]
+    fn test_display_grid() {
+        let answer = Answer::Grid(vec![String::from("#."), String::from(".#")]);
+        assert_eq!("#.\n.#", answer.to_string());
+        assert!(answer.is_multiline())
+    }
+}
@@ -0,0 +1,322 @@
+use std::fs;
+use std::io;
+use std::time::Duration;
+
+use crate::json;
+use crate::json::Value;
+use crate::registry::Task;
+use crate::runner;
+use crate::runner::RunError;
+use crate::runner::format_duration;
+
+/// Where benchmark results are kept between runs, in the crate's directory
+/// whatever the working directory.
+pub static BASELINE_FILEPATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/bench_baseline.json");
+
+/// Median slowdown (in percent) above which a run is flagged as a regression.
+pub static REGRESSION_THRESHOLD: f64 = 10.0;
+
+/// Median slowdowns up to this are never flagged, below it run-to-run noise
+/// of sub-millisecond phases exceeds [`REGRESSION_THRESHOLD`].
+pub static REGRESSION_FLOOR: Duration = Duration::from_micros(50);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "Expected at least one sample");
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+        // Nearest-rank percentile.
+        let p95_rank = (sorted.len() as f64 * 0.95).ceil() as usize;
+        Stats {
+            min: sorted[0],
+            median,
+            p95: sorted[p95_rank.max(1) - 1],
+        }
+    }
+
+    fn to_json(self) -> Value {
+        Value::Object(vec![
+            (String::from("min_ns"), Value::from(self.min.as_nanos() as f64)),
+            (String::from("median_ns"), Value::from(self.median.as_nanos() as f64)),
+            (String::from("p95_ns"), Value::from(self.p95.as_nanos() as f64)),
+        ])
+    }
+
+    fn from_json(value: &Value) -> Option<Self> {
+        let nanos = |key: &str| Some(Duration::from_nanos(value.get(key)?.as_f64()? as u64));
+        Some(Stats {
+            min: nanos("min_ns")?,
+            median: nanos("median_ns")?,
+            p95: nanos("p95_ns")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchResult {
+    pub task_id: String,
+    pub input_path: String,
+    pub runs: usize,
+    pub parse: Stats,
+    pub solve: Stats,
+    pub total: Stats,
+}
+
+impl BenchResult {
+    fn to_json(&self) -> Value {
+        Value::Object(vec![
+            (String::from("input"), Value::from(self.input_path.as_str())),
+            (String::from("runs"), Value::from(self.runs as i64)),
+            (String::from("parse"), self.parse.to_json()),
+            (String::from("solve"), self.solve.to_json()),
+            (String::from("total"), self.total.to_json()),
+        ])
+    }
+
+    fn from_json(task_id: &str, value: &Value) -> Option<Self> {
+        Some(BenchResult {
+            task_id: task_id.to_string(),
+            input_path: value.get("input")?.as_str()?.to_string(),
+            runs: value.get("runs")?.as_f64()? as usize,
+            parse: Stats::from_json(value.get("parse")?)?,
+            solve: Stats::from_json(value.get("solve")?)?,
+            total: Stats::from_json(value.get("total")?)?,
+        })
+    }
+}
+
+/// Runs `task` `warmup` times untimed and then `runs` times timed, against
+/// `input_path` or the task's default input.
+///
+/// The input is read into memory once, so file I/O is not measured.
+pub fn bench_task(task: &Task, input_path: Option<&str>, warmup: usize, runs: usize) -> Result<BenchResult, RunError> {
+    let raw = runner::read_task_input(task, input_path)?;
+    let input_path = input_path.unwrap_or(&task.data_path);
+    let run = |raw: &str| runner::run_task_with(task, raw).map_err(|err| RunError::input(err, input_path));
+
+    for _ in 0..warmup {
+        run(&raw)?;
+    }
+
+    let mut parse = Vec::new();
+    let mut solve = Vec::new();
+    let mut total = Vec::new();
+    for _ in 0..runs {
+        let report = run(&raw)?;
+        parse.push(report.parse_duration);
+        solve.push(report.solve_duration());
+        total.push(report.duration);
+    }
+
+    Ok(BenchResult {
+        task_id: task.id.to_string(),
+        input_path: input_path.to_string(),
+        runs,
+        parse: Stats::from_samples(&parse),
+        solve: Stats::from_samples(&solve),
+        total: Stats::from_samples(&total),
+    })
+}
+
+/// Baseline results keyed by task id.
+#[derive(Debug, Default)]
+pub struct Baseline {
+    results: Vec<BenchResult>,
+}
+
+impl Baseline {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let raw = match fs::read_to_string(path) {
+            Ok(raw) => raw,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Baseline::default()),
+            Err(err) => return Err(format!("Failed to read {}: {}", path, err)),
+        };
+        match json::parse(&raw)? {
+            Value::Object(entries) => entries
+                .iter()
+                .map(|(task_id, value)| {
+                    BenchResult::from_json(task_id, value)
+                        .ok_or_else(|| format!("Invalid baseline entry for {} in {}", task_id, path))
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(|results| Baseline { results }),
+            _ => Err(format!("Expected {} to contain a JSON object", path)),
+        }
+    }
+
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        let mut raw = String::from("{\n");
+        for (idx, result) in self.results.iter().enumerate() {
+            let separator = if idx + 1 < self.results.len() { "," } else { "" };
+            raw.push_str(&format!(
+                "  {}: {}{}\n",
+                Value::from(result.task_id.as_str()),
+                result.to_json(),
+                separator
+            ));
+        }
+        raw.push_str("}\n");
+        fs::write(path, raw)
+    }
+
+    pub fn get(&self, task_id: &str) -> Option<&BenchResult> {
+        self.results.iter().find(|r| r.task_id == task_id)
+    }
+
+    pub fn set(&mut self, result: BenchResult) {
+        match self.results.iter_mut().find(|r| r.task_id == result.task_id) {
+            Some(existing) => *existing = result,
+            None => self.results.push(result),
+        }
+    }
+}
+
+/// Median change against the baseline, in percent.
+pub fn change_percent(baseline: Duration, current: Duration) -> f64 {
+    if baseline.is_zero() {
+        return 0.0;
+    }
+    (current.as_secs_f64() - baseline.as_secs_f64()) / baseline.as_secs_f64() * 100.0
+}
+
+pub fn format_result(result: &BenchResult) -> String {
+    let mut output = format!("{:<6}  {:>12}  {:>12}  {:>12}\n", "phase", "min", "median", "p95");
+    for (phase, stats) in [("parse", result.parse), ("solve", result.solve), ("total", result.total)] {
+        output.push_str(&format!(
+            "{:<6}  {:>12}  {:>12}  {:>12}\n",
+            phase,
+            format_duration(stats.min),
+            format_duration(stats.median),
+            format_duration(stats.p95)
+        ));
+    }
+    output
+}
+
+/// Compares medians against the baseline, returns report lines and whether
+/// any phase regressed by more than [`REGRESSION_THRESHOLD`] and more than
+/// [`REGRESSION_FLOOR`].
+pub fn compare(baseline: &BenchResult, current: &BenchResult) -> (String, bool) {
+    let mut output = String::new();
+    let mut regressed = false;
+    for (phase, before, after) in [
+        ("parse", baseline.parse.median, current.parse.median),
+        ("solve", baseline.solve.median, current.solve.median),
+        ("total", baseline.total.median, current.total.median),
+    ] {
+        let change = change_percent(before, after);
+        let flag = if change > REGRESSION_THRESHOLD && after.saturating_sub(before) > REGRESSION_FLOOR {
+            regressed = true;
+            "  REGRESSION"
+        } else {
+            ""
+        };
+        output.push_str(&format!(
+            "{:<6}  {:>12} -> {:>12}  {:+.1}%{}\n",
+            phase,
+            format_duration(before),
+            format_duration(after),
+            change,
+            flag
+        ));
+    }
+    (output, regressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(ms: u64) -> Stats {
+        let d = Duration::from_millis(ms);
+        Stats { min: d, median: d, p95: d }
+    }
+
+    fn result(task_id: &str, solve_ms: u64) -> BenchResult {
+        BenchResult {
+            task_id: task_id.to_string(),
+            input_path: String::from("data/day7.txt"),
+            runs: 10,
+            parse: stats(1),
+            solve: stats(solve_ms),
+            total: stats(1 + solve_ms),
+        }
+    }
+
+    #[test]
+    fn test_stats_from_samples() {
+        let samples: Vec<Duration> = (1..=20).rev().map(Duration::from_millis).collect();
+        let stats = Stats::from_samples(&samples);
+        assert_eq!(Duration::from_millis(1), stats.min);
+        assert_eq!(Duration::from_micros(10500), stats.median);
+        assert_eq!(Duration::from_millis(19), stats.p95);
+    }
+
+    #[test]
+    fn test_stats_single_sample() {
+        let stats = Stats::from_samples(&[Duration::from_millis(3)]);
+        assert_eq!(stats, super::tests::stats(3))
+    }
+
+    #[test]
+    fn test_result_json_roundtrip() {
+        let original = result("day7_task2", 5);
+        let parsed = BenchResult::from_json("day7_task2", &original.to_json()).unwrap();
+        assert_eq!(original, parsed)
+    }
+
+    #[test]
+    fn test_baseline_save_and_load() {
+        let path = std::env::temp_dir().join("aoc2020_bench_baseline_test.json");
+        let path = path.to_str().unwrap();
+        let mut baseline = Baseline::default();
+        baseline.set(result("day7_task1", 3));
+        baseline.set(result("day7_task2", 5));
+        baseline.set(result("day7_task1", 4));
+        baseline.save(path).unwrap();
+
+        let loaded = Baseline::load(path).unwrap();
+        fs::remove_file(path).unwrap();
+        assert_eq!(Some(&result("day7_task1", 4)), loaded.get("day7_task1"));
+        assert_eq!(Some(&result("day7_task2", 5)), loaded.get("day7_task2"));
+    }
+
+    #[test]
+    fn test_compare_flags_regression() {
+        let (_, regressed) = compare(&result("day7_task2", 10), &result("day7_task2", 10));
+        assert!(!regressed);
+        let (report, regressed) = compare(&result("day7_task2", 10), &result("day7_task2", 20));
+        assert!(regressed);
+        assert!(report.contains("REGRESSION"))
+    }
+
+    #[test]
+    fn test_compare_ignores_noise_below_the_floor() {
+        let with_parse = |parse: Stats| BenchResult {
+            parse,
+            ..result("day7_task2", 10)
+        };
+        let micros = |us| {
+            let d = Duration::from_micros(us);
+            Stats { min: d, median: d, p95: d }
+        };
+        let (report, regressed) = compare(&with_parse(micros(100)), &with_parse(micros(140)));
+        assert!(!regressed, "{}", report);
+        assert!(report.contains("+40.0%"));
+        let (_, regressed) = compare(&with_parse(micros(100)), &with_parse(micros(200)));
+        assert!(regressed);
+    }
+}
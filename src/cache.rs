@@ -0,0 +1,303 @@
+//! Answers of earlier runs, reused while neither the binary nor the input of a
+//! task changed, see `--no-cache`.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::time::SystemTime;
+
+use crate::answer::Answer;
+use crate::json;
+use crate::json::Value;
+
+/// Environment variable overriding where the cache is kept.
+pub static CACHE_FILE_ENV: &str = "AOC_CACHE_FILE";
+
+/// Name of the cache in the user's cache directory.
+pub static CACHE_FILENAME: &str = "aoc2020_answers.json";
+
+/// Most entries kept on saving, those of the running binary first and then the
+/// most recently answered ones of other builds sharing the file.
+pub static MAX_ENTRIES: usize = 4096;
+
+static FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+static FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// 64-bit FNV-1a hash of `bytes`, continuing from `hash`.
+pub fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(hash, |hash, byte| (hash ^ *byte as u64).wrapping_mul(FNV_PRIME))
+}
+
+/// Hash of the size and modification time of the running executable, so that
+/// rebuilding invalidates every entry without reading the whole binary. Falls
+/// back to the package version if the executable cannot be found.
+pub fn binary_version() -> u64 {
+    static VERSION: OnceLock<u64> = OnceLock::new();
+    *VERSION.get_or_init(|| match env::current_exe().and_then(fs::metadata) {
+        Ok(metadata) => {
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(SystemTime::UNIX_EPOCH).ok())
+                .unwrap_or_default();
+            let hash = fnv1a(FNV_OFFSET_BASIS, &metadata.len().to_le_bytes());
+            fnv1a(hash, &modified.as_nanos().to_le_bytes())
+        }
+        Err(_) => fnv1a(FNV_OFFSET_BASIS, env!("CARGO_PKG_VERSION").as_bytes()),
+    })
+}
+
+/// Picks the cache from `AOC_CACHE_FILE`, then [`CACHE_FILENAME`] in
+/// `XDG_CACHE_HOME` or `~/.cache`, `None` when none of them is set.
+pub fn cache_file() -> Option<PathBuf> {
+    resolve_cache_file(|name| env::var_os(name))
+}
+
+fn resolve_cache_file(var: impl Fn(&str) -> Option<OsString>) -> Option<PathBuf> {
+    let var = |name: &str| var(name).filter(|value| !value.is_empty()).map(PathBuf::from);
+    var(CACHE_FILE_ENV)
+        .or_else(|| var("XDG_CACHE_HOME").map(|dir| dir.join(CACHE_FILENAME)))
+        .or_else(|| var("HOME").map(|home| home.join(".cache").join(CACHE_FILENAME)))
+}
+
+/// Seconds since the Unix epoch, when entries were answered.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|since| since.as_secs())
+        .unwrap_or_default()
+}
+
+/// Key of the answer of `task_id` on `raw` produced by the binary `version`.
+pub fn cache_key(version: u64, task_id: &str, raw: &str) -> String {
+    let mut hash = fnv1a(FNV_OFFSET_BASIS, &version.to_le_bytes());
+    hash = fnv1a(hash, task_id.as_bytes());
+    hash = fnv1a(hash, &[0]);
+    hash = fnv1a(hash, raw.as_bytes());
+    format!("{:016x}", hash)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    /// Kept to make the file readable.
+    task_id: String,
+    /// The [`binary_version`] that answered, only entries of the running
+    /// binary can be hit.
+    version: u64,
+    /// Seconds since the Unix epoch, picks the entries of other versions kept
+    /// up to [`MAX_ENTRIES`].
+    answered: u64,
+    answer: Answer,
+}
+
+/// Answers keyed by [`cache_key`], shared by the threads running tasks.
+#[derive(Debug, Default)]
+pub struct AnswerCache {
+    entries: Mutex<BTreeMap<String, Entry>>,
+}
+
+impl AnswerCache {
+    /// Reads the cache at `path`, a missing file is an empty cache. Entries
+    /// without a version, written before versions were recorded, are skipped.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let path_display = path.display();
+        let raw = match fs::read_to_string(path) {
+            Ok(raw) => raw,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(AnswerCache::default()),
+            Err(err) => return Err(format!("Failed to read {}: {}", path_display, err)),
+        };
+        let invalid = |key: &str| format!("Invalid cache entry {} in {}", key, path_display);
+        match json::parse(&raw)? {
+            Value::Object(entries) => entries
+                .iter()
+                .filter(|(_, value)| value.get("version").is_some())
+                .map(|(key, value)| {
+                    let task_id = value.get("task").and_then(Value::as_str).ok_or_else(|| invalid(key))?;
+                    let version = value
+                        .get("version")
+                        .and_then(Value::as_str)
+                        .and_then(|version| u64::from_str_radix(version, 16).ok())
+                        .ok_or_else(|| invalid(key))?;
+                    let answered = match value.get("answered") {
+                        Some(answered) => answered.as_i64().ok_or_else(|| invalid(key))? as u64,
+                        None => 0,
+                    };
+                    let answer = value.get("answer").and_then(Answer::from_json).ok_or_else(|| invalid(key))?;
+                    let entry = Entry {
+                        task_id: task_id.to_string(),
+                        version,
+                        answered,
+                        answer,
+                    };
+                    Ok((key.to_string(), entry))
+                })
+                .collect::<Result<BTreeMap<_, _>, _>>()
+                .map(|entries| AnswerCache {
+                    entries: Mutex::new(entries),
+                }),
+            _ => Err(format!("Expected {} to contain a JSON object", path_display)),
+        }
+    }
+
+    /// Writes up to [`MAX_ENTRIES`] entries, so that builds sharing the file
+    /// keep their answers as long as it is not full.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() > MAX_ENTRIES {
+            let mut order: Vec<(bool, u64, String)> = entries
+                .iter()
+                .map(|(key, entry)| (entry.version == binary_version(), entry.answered, key.clone()))
+                .collect();
+            order.sort_by(|a, b| b.cmp(a));
+            for (_, _, key) in order.drain(MAX_ENTRIES..) {
+                entries.remove(&key);
+            }
+        }
+        let mut raw = String::from("{\n");
+        for (idx, (key, entry)) in entries.iter().enumerate() {
+            let separator = if idx + 1 < entries.len() { "," } else { "" };
+            let entry = Value::Object(vec![
+                (String::from("task"), Value::from(entry.task_id.as_str())),
+                (String::from("version"), Value::from(format!("{:016x}", entry.version))),
+                (String::from("answered"), Value::from(entry.answered as i64)),
+                (String::from("answer"), entry.answer.to_json()),
+            ]);
+            raw.push_str(&format!("  {}: {}{}\n", Value::from(key.as_str()), entry, separator));
+        }
+        raw.push_str("}\n");
+        fs::write(path, raw)
+    }
+
+    pub fn get(&self, task_id: &str, raw: &str) -> Option<Answer> {
+        let key = cache_key(binary_version(), task_id, raw);
+        self.entries.lock().unwrap().get(&key).map(|entry| entry.answer.clone())
+    }
+
+    pub fn insert(&self, task_id: &str, raw: &str, answer: Answer) {
+        self.insert_version(binary_version(), now(), task_id, raw, answer)
+    }
+
+    fn insert_version(&self, version: u64, answered: u64, task_id: &str, raw: &str, answer: Answer) {
+        let entry = Entry {
+            task_id: task_id.to_string(),
+            version,
+            answered,
+            answer,
+        };
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(cache_key(version, task_id, raw), entry);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(0xcbf2_9ce4_8422_2325, fnv1a(FNV_OFFSET_BASIS, b""));
+        assert_eq!(0xaf63_dc4c_8601_ec8c, fnv1a(FNV_OFFSET_BASIS, b"a"));
+        assert_eq!(0x8594_4171_f739_67e8, fnv1a(FNV_OFFSET_BASIS, b"foobar"));
+    }
+
+    #[test]
+    fn test_cache_key() {
+        let key = cache_key(1, "2021/5/1", "0,9 -> 5,9\n");
+        assert_eq!(16, key.len());
+        assert_eq!(key, cache_key(1, "2021/5/1", "0,9 -> 5,9\n"));
+        assert_ne!(key, cache_key(2, "2021/5/1", "0,9 -> 5,9\n"));
+        assert_ne!(key, cache_key(1, "2021/5/2", "0,9 -> 5,9\n"));
+        assert_ne!(key, cache_key(1, "2021/5/1", "0,9 -> 5,8\n"));
+    }
+
+    #[test]
+    fn test_resolve_cache_file() {
+        let vars = |set: &'static [(&'static str, &'static str)]| {
+            move |name: &str| set.iter().find(|(key, _)| *key == name).map(|(_, value)| OsString::from(value))
+        };
+        assert_eq!(
+            Some(PathBuf::from("/tmp/c.json")),
+            resolve_cache_file(vars(&[("AOC_CACHE_FILE", "/tmp/c.json"), ("HOME", "/home/a")]))
+        );
+        assert_eq!(
+            Some(Path::new("/xdg").join(CACHE_FILENAME)),
+            resolve_cache_file(vars(&[("AOC_CACHE_FILE", ""), ("XDG_CACHE_HOME", "/xdg"), ("HOME", "/home/a")]))
+        );
+        assert_eq!(
+            Some(Path::new("/home/a/.cache").join(CACHE_FILENAME)),
+            resolve_cache_file(vars(&[("HOME", "/home/a")]))
+        );
+        assert_eq!(None, resolve_cache_file(vars(&[])));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = env::temp_dir().join("aoc2020_cache_test.json");
+        let _ = fs::remove_file(&path);
+        assert!(AnswerCache::load(&path).unwrap().is_empty());
+
+        let cache = AnswerCache::default();
+        cache.insert("2021/13/2", "x", Answer::Grid(vec![String::from("#."), String::from(".#")]));
+        cache.insert("2021/1/1", "1\n2\n", Answer::Int(1226));
+        cache.insert("2021/6/2", "3,4\n", Answer::Int(9007199254740993));
+        cache.insert_version(binary_version() ^ 1, now(), "2021/1/2", "1\n2\n", Answer::Int(1));
+        cache.save(&path).unwrap();
+
+        let cache = AnswerCache::load(&path).unwrap();
+        assert_eq!(4, cache.len(), "entries of other binaries are kept");
+        assert_eq!(Some(Answer::Int(1226)), cache.get("2021/1/1", "1\n2\n"));
+        assert_eq!(Some(Answer::Int(9007199254740993)), cache.get("2021/6/2", "3,4\n"));
+        assert_eq!(None, cache.get("2021/1/1", "1\n3\n"));
+        assert_eq!(None, cache.get("2021/1/2", "1\n2\n"));
+        assert!(cache.get("2021/13/2", "x").unwrap().is_multiline());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_save_keeps_the_running_binary_and_recent_entries() {
+        let path = env::temp_dir().join("aoc2020_cache_bounded_test.json");
+        let cache = AnswerCache::default();
+        for idx in 0..MAX_ENTRIES as u64 {
+            cache.insert_version(binary_version() ^ 1, idx, "2021/1/1", &idx.to_string(), Answer::Int(1));
+        }
+        cache.insert_version(binary_version() ^ 2, 0, "2021/1/1", "oldest", Answer::Int(2));
+        cache.insert_version(binary_version(), 0, "2021/1/1", "current", Answer::Int(3));
+        cache.save(&path).unwrap();
+
+        let cache = AnswerCache::load(&path).unwrap();
+        assert_eq!(MAX_ENTRIES, cache.len());
+        assert_eq!(Some(Answer::Int(3)), cache.get("2021/1/1", "current"));
+        let keys = cache.entries.lock().unwrap();
+        assert!(!keys.contains_key(&cache_key(binary_version() ^ 2, "2021/1/1", "oldest")));
+        assert!(!keys.contains_key(&cache_key(binary_version() ^ 1, "2021/1/1", "0")));
+        assert!(keys.contains_key(&cache_key(binary_version() ^ 1, "2021/1/1", "1")));
+        drop(keys);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_load_skips_unversioned_entries() {
+        let path = env::temp_dir().join("aoc2020_cache_unversioned_test.json");
+        fs::write(&path, r#"{"00": {"task": "2021/1/1", "answer": 7}}"#).unwrap();
+        assert!(AnswerCache::load(&path).unwrap().is_empty());
+        fs::remove_file(&path).unwrap();
+    }
+}
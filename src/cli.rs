@@ -0,0 +1,561 @@
+use std::time::Duration;
+
+use crate::registry::ALIAS_YEAR;
+
+/// Command line arguments of the runner.
+///
+/// Usage:
+/// - `aoc2020 [task_id] [--input <path>]` runs a single task, `<path>` can be
+///   `-` to read the input from stdin. Tasks are addressed as `2021/3/1`,
+///   `task_id` can also be a whole day (`2021/3`), a range of days of one year
+///   (`2021/3..2021/7`) or a whole year (`2021`). The older `day3_task1`,
+///   `day3` and `day3..day7` forms are aliases for 2021.
+/// - `aoc2020 all [--jobs N]` runs every registered task, spread over `N`
+///   threads, and prints a summary table.
+/// - Both of the above accept `--format json` to print one JSON object per
+///   task instead, and `--timeout <seconds>` to give up on tasks running
+///   longer than that. Tasks that panic or time out are reported and the exit
+///   code has bit 1 set for panics and bit 4 for timeouts. `--alloc-stats`
+///   adds the allocation count, bytes allocated and peak bytes of each task.
+/// - Both also answer tasks whose input and binary are unchanged since an
+///   earlier run, marked as cached in the output. The cache is
+///   `$AOC_CACHE_FILE`, or `aoc2020_answers.json` in `$XDG_CACHE_HOME` or
+///   `~/.cache`. `--no-cache` runs them anyway, as does `--alloc-stats`.
+/// - `aoc2020 bench <task_id> [--input <path>] [--runs N] [--warmup N] [--save]`
+///   benchmarks a task and compares it against the baseline saved in
+///   `bench_baseline.json` in the crate's directory.
+/// - `aoc2020 verify [--answers <path>]` checks every task against the
+///   expected answers file.
+/// - `aoc2020 compare [task_id] [--input <path>]` runs the default solver and
+///   the variants of the selected tasks, or of every task that has variants,
+///   on the same input and prints their answers and times. The exit code has
+///   bit 8 set when they disagree and bit 1 when one panics.
+/// - `aoc2020 generate <day> [--seed N] [--size N]` prints a random input of
+///   the day, the same for the same seed, which defaults to one based on the
+///   time and is printed on stderr. `--size` scales the puzzle, e.g. the
+///   number of lines.
+/// - `aoc2020 python [task_id] [--input <path>]` runs the days that only
+///   exist as `src/dayN.py` scripts with `python3` and reports them like
+///   tasks, accepting `--format json` and `--timeout` too. Parts that also
+///   have a Rust task are solved by both on the same input, the exit code has
+///   bit 8 set when their answers differ.
+/// - `aoc2020 repl <day> [--input <path>]` parses a day's input once and then
+///   reads commands like `part1`, `reload`, `stats` or day-specific ones like
+///   `step 10`, see `help` inside the REPL.
+/// - `aoc2020 serve [--port N] [--timeout <seconds>]` answers
+///   `POST /year/{y}/day/{n}/part/{m}` requests on `127.0.0.1`, with the
+///   puzzle input as the body, see [`crate::serve`]. Requests get a 503 while
+///   `serve::MAX_SOLVERS` solvers, timed out ones included, are running.
+/// - `aoc2020 new [<year>/]<day>` scaffolds `src/yYEAR/dayN.rs` and its data
+///   files from `src/dayTEMPLATE.rs` in the crate's directory, and adds
+///   placeholder answers to `data/answers.json`. The year defaults to 2021.
+///
+/// Except for `new`, all commands accept `--data-dir <dir>` to read the
+/// default inputs and answers from `<dir>` instead of `$AOC_DATA_DIR` or the
+/// crate's `data` directory. Binaries built with the `embedded-inputs` feature
+/// use their compiled-in copies of the default inputs, `--input` still reads
+/// the given file.
+///
+/// `-v` prints what the runner does on stderr, `-vv` also the solvers' debug
+/// dumps.
+#[derive(Debug, Default, PartialEq)]
+pub struct Args {
+    pub command: Command,
+    pub input: Option<String>,
+    pub format: Format,
+    /// Time budget of every task.
+    pub timeout: Option<Duration>,
+    pub data_dir: Option<String>,
+    /// Number of `-v` flags, see [`crate::log`].
+    pub verbosity: u8,
+    pub alloc_stats: bool,
+    /// Runs every task instead of reusing cached answers, see
+    /// [`crate::cache`].
+    pub no_cache: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Format {
+    #[default]
+    Text,
+    /// JSON Lines, one object per task.
+    Json,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run {
+        task_id: Option<String>,
+    },
+    All {
+        jobs: usize,
+    },
+    Bench {
+        task_id: String,
+        warmup: usize,
+        runs: usize,
+        save: bool,
+    },
+    Verify {
+        answers: Option<String>,
+    },
+    /// Every task with variants unless `task_id` selects some.
+    Compare {
+        task_id: Option<String>,
+    },
+    Generate {
+        day: String,
+        /// Picked by the caller when missing.
+        seed: Option<u64>,
+        size: usize,
+    },
+    /// Every part of every script unless `task_id` selects some.
+    Python {
+        task_id: Option<String>,
+    },
+    Repl {
+        day: String,
+    },
+    Serve {
+        port: u16,
+    },
+    New {
+        year: u32,
+        day: u32,
+    },
+}
+
+impl Default for Command {
+    fn default() -> Self {
+        Command::Run { task_id: None }
+    }
+}
+
+pub static DEFAULT_BENCH_WARMUP: usize = 3;
+pub static DEFAULT_BENCH_RUNS: usize = 10;
+pub static DEFAULT_JOBS: usize = 1;
+pub static DEFAULT_PORT: u16 = 8020;
+pub static DEFAULT_GENERATE_SIZE: usize = 10;
+
+fn parse_count(option: &str, value: Option<String>) -> Result<usize, String> {
+    let value = value.ok_or_else(|| format!("Missing value for {}", option))?;
+    match value.parse() {
+        Ok(count) if count > 0 || option == "--warmup" => Ok(count),
+        _ => Err(format!("Invalid value for {}: {}", option, value)),
+    }
+}
+
+impl Args {
+    pub fn parse(args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut input: Option<String> = None;
+        let mut format: Option<Format> = None;
+        let mut warmup: Option<usize> = None;
+        let mut runs: Option<usize> = None;
+        let mut save = false;
+        let mut answers: Option<String> = None;
+        let mut jobs: Option<usize> = None;
+        let mut timeout: Option<Duration> = None;
+        let mut data_dir: Option<String> = None;
+        let mut verbosity: u8 = 0;
+        let mut port: Option<u16> = None;
+        let mut seed: Option<u64> = None;
+        let mut size: Option<usize> = None;
+        let mut alloc_stats = false;
+        let mut no_cache = false;
+        let mut positional: Vec<String> = Vec::new();
+        let mut args = args;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" | "-i" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| format!("Missing value for {}", arg))?;
+                    input = Some(path);
+                }
+                "--format" => {
+                    format = match args.next().as_deref() {
+                        Some("text") => Some(Format::Text),
+                        Some("json") => Some(Format::Json),
+                        Some(value) => return Err(format!("Invalid value for {}: {}", arg, value)),
+                        None => return Err(format!("Missing value for {}", arg)),
+                    }
+                }
+                "--warmup" => warmup = Some(parse_count(&arg, args.next())?),
+                "--runs" => runs = Some(parse_count(&arg, args.next())?),
+                "--save" => save = true,
+                "--alloc-stats" => alloc_stats = true,
+                "--no-cache" => no_cache = true,
+                "-v" => verbosity += 1,
+                "-vv" => verbosity += 2,
+                "--jobs" | "-j" => jobs = Some(parse_count(&arg, args.next())?),
+                "--size" => size = Some(parse_count(&arg, args.next())?),
+                "--seed" => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| format!("Missing value for {}", arg))?;
+                    seed = Some(value.parse().map_err(|_| format!("Invalid value for {}: {}", arg, value))?);
+                }
+                "--timeout" => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| format!("Missing value for {}", arg))?;
+                    timeout = match value.parse::<f64>() {
+                        Ok(seconds) if seconds > 0.0 && seconds.is_finite() => Some(Duration::from_secs_f64(seconds)),
+                        _ => return Err(format!("Invalid value for {}: {}", arg, value)),
+                    }
+                }
+                "--port" => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| format!("Missing value for {}", arg))?;
+                    port = Some(value.parse().map_err(|_| format!("Invalid value for {}: {}", arg, value))?);
+                }
+                "--data-dir" => {
+                    let dir = args
+                        .next()
+                        .ok_or_else(|| format!("Missing value for {}", arg))?;
+                    data_dir = Some(dir);
+                }
+                "--answers" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| format!("Missing value for {}", arg))?;
+                    answers = Some(path);
+                }
+                _ if arg.starts_with('-') && arg.len() > 1 => {
+                    return Err(format!("Unknown option: {}", arg));
+                }
+                _ => positional.push(arg),
+            }
+        }
+
+        let mut positional = positional.into_iter();
+        let command = match positional.next() {
+            Some(command) if command == "all" => {
+                if input.is_some() {
+                    return Err(String::from("--input cannot be used with all"));
+                }
+                Command::All {
+                    jobs: jobs.take().unwrap_or(DEFAULT_JOBS),
+                }
+            }
+            Some(command) if command == "bench" => {
+                let task_id = positional
+                    .next()
+                    .ok_or_else(|| String::from("bench requires a task_id"))?;
+                Command::Bench {
+                    task_id,
+                    warmup: warmup.take().unwrap_or(DEFAULT_BENCH_WARMUP),
+                    runs: runs.take().unwrap_or(DEFAULT_BENCH_RUNS),
+                    save: std::mem::take(&mut save),
+                }
+            }
+            Some(command) if command == "verify" => {
+                if input.is_some() {
+                    return Err(String::from("--input cannot be used with verify"));
+                }
+                Command::Verify {
+                    answers: answers.take(),
+                }
+            }
+            Some(command) if command == "compare" => Command::Compare {
+                task_id: positional.next(),
+            },
+            Some(command) if command == "generate" => {
+                if input.is_some() {
+                    return Err(String::from("--input cannot be used with generate"));
+                }
+                let day = positional
+                    .next()
+                    .ok_or_else(|| String::from("generate requires a day, e.g. 2021/14"))?;
+                Command::Generate {
+                    day,
+                    seed: seed.take(),
+                    size: size.take().unwrap_or(DEFAULT_GENERATE_SIZE),
+                }
+            }
+            Some(command) if command == "python" => Command::Python {
+                task_id: positional.next(),
+            },
+            Some(command) if command == "repl" => {
+                let day = positional
+                    .next()
+                    .ok_or_else(|| String::from("repl requires a day, e.g. day14"))?;
+                if input.as_deref() == Some("-") {
+                    return Err(String::from("repl reads its commands from stdin, --input cannot be -"));
+                }
+                Command::Repl { day }
+            }
+            Some(command) if command == "serve" => {
+                if input.is_some() {
+                    return Err(String::from("--input cannot be used with serve"));
+                }
+                Command::Serve {
+                    port: port.take().unwrap_or(DEFAULT_PORT),
+                }
+            }
+            Some(command) if command == "new" => {
+                if input.is_some() || data_dir.is_some() {
+                    return Err(String::from("--input and --data-dir cannot be used with new"));
+                }
+                let spec = positional
+                    .next()
+                    .ok_or_else(|| String::from("new requires a day number"))?;
+                let (year, day) = match spec.split_once('/') {
+                    Some((year, day)) => (year.parse().map_err(|_| format!("Invalid year: {}", year))?, day),
+                    None => (ALIAS_YEAR, spec.as_str()),
+                };
+                match day.parse() {
+                    Ok(day) if (1..=25).contains(&day) => Command::New { year, day },
+                    _ => return Err(format!("Invalid day: {}", day)),
+                }
+            }
+            task_id => Command::Run { task_id },
+        };
+        if let Some(arg) = positional.next() {
+            return Err(format!("Unexpected argument: {}", arg));
+        }
+        if warmup.is_some() || runs.is_some() || save {
+            return Err(String::from("--warmup, --runs and --save can only be used with bench"));
+        }
+        if answers.is_some() {
+            return Err(String::from("--answers can only be used with verify"));
+        }
+        if jobs.is_some() {
+            return Err(String::from("--jobs can only be used with all"));
+        }
+        if port.is_some() {
+            return Err(String::from("--port can only be used with serve"));
+        }
+        if seed.is_some() || size.is_some() {
+            return Err(String::from("--seed and --size can only be used with generate"));
+        }
+        if format.is_some() && !matches!(command, Command::Run { .. } | Command::All { .. } | Command::Python { .. }) {
+            return Err(String::from("--format can only be used with a task, all or python"));
+        }
+        if (alloc_stats || no_cache) && !matches!(command, Command::Run { .. } | Command::All { .. }) {
+            return Err(String::from("--alloc-stats and --no-cache can only be used with a task or all"));
+        }
+        if timeout.is_some()
+            && !matches!(command, Command::Run { .. } | Command::All { .. } | Command::Serve { .. } | Command::Python { .. })
+        {
+            return Err(String::from("--timeout can only be used with a task, all, serve or python"));
+        }
+        Ok(Args {
+            command,
+            input,
+            format: format.unwrap_or_default(),
+            timeout,
+            data_dir,
+            verbosity,
+            alloc_stats,
+            no_cache,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        assert_eq!(Ok(Args::default()), parse(&[]))
+    }
+
+    #[test]
+    fn test_parse_task_and_input() {
+        let expected = Args {
+            command: Command::Run {
+                task_id: Some(String::from("day6_task2")),
+            },
+            input: Some(String::from("-")),
+            format: Format::Text,
+            timeout: None,
+            data_dir: None,
+            verbosity: 0,
+            alloc_stats: false,
+            no_cache: false,
+        };
+        assert_eq!(Ok(expected), parse(&["day6_task2", "--input", "-"]))
+    }
+
+    #[test]
+    fn test_parse_missing_input_value() {
+        assert!(parse(&["day6_task2", "--input"]).is_err())
+    }
+
+    #[test]
+    fn test_parse_unknown_option() {
+        assert!(parse(&["--verbose"]).is_err())
+    }
+
+    #[test]
+    fn test_parse_all() {
+        assert_eq!(Command::All { jobs: DEFAULT_JOBS }, parse(&["all"]).unwrap().command);
+        assert_eq!(Command::All { jobs: 4 }, parse(&["all", "--jobs", "4"]).unwrap().command);
+        assert!(parse(&["all", "--jobs", "0"]).is_err());
+        assert!(parse(&["day3", "--jobs", "4"]).is_err());
+        assert!(parse(&["all", "--input", "-"]).is_err())
+    }
+
+    #[test]
+    fn test_parse_timeout() {
+        assert_eq!(Some(Duration::from_millis(1500)), parse(&["all", "--timeout", "1.5"]).unwrap().timeout);
+        assert_eq!(None, parse(&["day3"]).unwrap().timeout);
+        assert!(parse(&["day3", "--timeout", "0"]).is_err());
+        assert!(parse(&["verify", "--timeout", "2"]).is_err())
+    }
+
+    #[test]
+    fn test_parse_data_dir() {
+        assert_eq!(Some(String::from("/tmp/aoc")), parse(&["all", "--data-dir", "/tmp/aoc"]).unwrap().data_dir);
+        assert_eq!(None, parse(&["verify"]).unwrap().data_dir);
+        assert!(parse(&["verify", "--data-dir"]).is_err());
+        assert!(parse(&["new", "17", "--data-dir", "/tmp/aoc"]).is_err())
+    }
+
+    #[test]
+    fn test_parse_verbosity() {
+        assert_eq!(0, parse(&["day3"]).unwrap().verbosity);
+        assert_eq!(1, parse(&["day3", "-v"]).unwrap().verbosity);
+        assert_eq!(2, parse(&["-vv", "all"]).unwrap().verbosity);
+        assert!(parse(&["-vvv"]).is_err())
+    }
+
+    #[test]
+    fn test_parse_alloc_stats() {
+        assert!(parse(&["all", "--alloc-stats"]).unwrap().alloc_stats);
+        assert!(!parse(&["all"]).unwrap().alloc_stats);
+        assert!(parse(&["bench", "day3_task1", "--alloc-stats"]).is_err())
+    }
+
+    #[test]
+    fn test_parse_no_cache() {
+        assert!(parse(&["2021/3", "--no-cache"]).unwrap().no_cache);
+        assert!(!parse(&["all"]).unwrap().no_cache);
+        assert!(parse(&["verify", "--no-cache"]).is_err())
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(Format::Json, parse(&["all", "--format", "json"]).unwrap().format);
+        assert_eq!(Format::Json, parse(&["--format", "json", "day3_task1"]).unwrap().format);
+        assert!(parse(&["--format", "yaml"]).is_err());
+        assert!(parse(&["verify", "--format", "json"]).is_err())
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        let expected = Command::Bench {
+            task_id: String::from("day7_task2"),
+            warmup: DEFAULT_BENCH_WARMUP,
+            runs: 50,
+            save: true,
+        };
+        assert_eq!(expected, parse(&["bench", "day7_task2", "--runs", "50", "--save"]).unwrap().command);
+        assert!(parse(&["bench"]).is_err());
+        assert!(parse(&["bench", "day7_task2", "--runs", "0"]).is_err());
+        assert!(parse(&["day7_task2", "--runs", "5"]).is_err())
+    }
+
+    #[test]
+    fn test_parse_verify() {
+        assert_eq!(Command::Verify { answers: None }, parse(&["verify"]).unwrap().command);
+        assert_eq!(
+            Command::Verify {
+                answers: Some(String::from("mine.json"))
+            },
+            parse(&["verify", "--answers", "mine.json"]).unwrap().command
+        );
+        assert!(parse(&["all", "--answers", "mine.json"]).is_err())
+    }
+
+    #[test]
+    fn test_parse_compare() {
+        assert_eq!(Command::Compare { task_id: None }, parse(&["compare"]).unwrap().command);
+        let args = parse(&["compare", "2021/15", "--input", "-"]).unwrap();
+        assert_eq!(
+            Command::Compare {
+                task_id: Some(String::from("2021/15"))
+            },
+            args.command
+        );
+        assert_eq!(Some(String::from("-")), args.input);
+        assert!(parse(&["compare", "2021/15", "2021/7"]).is_err());
+        assert!(parse(&["compare", "--timeout", "1"]).is_err())
+    }
+
+    #[test]
+    fn test_parse_generate() {
+        let expected = Command::Generate {
+            day: String::from("2021/4"),
+            seed: Some(42),
+            size: 3,
+        };
+        assert_eq!(expected, parse(&["generate", "2021/4", "--seed", "42", "--size", "3"]).unwrap().command);
+        let expected = Command::Generate {
+            day: String::from("day4"),
+            seed: None,
+            size: DEFAULT_GENERATE_SIZE,
+        };
+        assert_eq!(expected, parse(&["generate", "day4"]).unwrap().command);
+        assert!(parse(&["generate"]).is_err());
+        assert!(parse(&["generate", "day4", "--size", "0"]).is_err());
+        assert!(parse(&["generate", "day4", "--seed", "-1"]).is_err());
+        assert!(parse(&["generate", "day4", "--input", "x"]).is_err());
+        assert!(parse(&["day4", "--seed", "1"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_python() {
+        assert_eq!(Command::Python { task_id: None }, parse(&["python"]).unwrap().command);
+        let args = parse(&["python", "2021/21", "--input", "mine.txt", "--format", "json", "--timeout", "5"]).unwrap();
+        assert_eq!(
+            Command::Python {
+                task_id: Some(String::from("2021/21"))
+            },
+            args.command
+        );
+        assert_eq!(Format::Json, args.format);
+        assert_eq!(Some(Duration::from_secs(5)), args.timeout);
+        assert!(parse(&["python", "2021/21", "2021/22"]).is_err());
+        assert!(parse(&["python", "--alloc-stats"]).is_err());
+        assert!(parse(&["python", "--no-cache"]).is_err())
+    }
+
+    #[test]
+    fn test_parse_repl() {
+        let args = parse(&["repl", "day14", "--input", "mine.txt"]).unwrap();
+        assert_eq!(Command::Repl { day: String::from("day14") }, args.command);
+        assert_eq!(Some(String::from("mine.txt")), args.input);
+        assert!(parse(&["repl"]).is_err());
+        assert!(parse(&["repl", "day14", "--input", "-"]).is_err());
+        assert!(parse(&["repl", "day14", "--format", "json"]).is_err())
+    }
+
+    #[test]
+    fn test_parse_serve() {
+        assert_eq!(Command::Serve { port: DEFAULT_PORT }, parse(&["serve"]).unwrap().command);
+        let args = parse(&["serve", "--port", "9000", "--timeout", "2"]).unwrap();
+        assert_eq!(Command::Serve { port: 9000 }, args.command);
+        assert_eq!(Some(Duration::from_secs(2)), args.timeout);
+        assert!(parse(&["serve", "--port", "70000"]).is_err());
+        assert!(parse(&["serve", "--format", "json"]).is_err());
+        assert!(parse(&["all", "--port", "9000"]).is_err())
+    }
+
+    #[test]
+    fn test_parse_new() {
+        assert_eq!(Command::New { year: 2021, day: 17 }, parse(&["new", "17"]).unwrap().command);
+        assert_eq!(Command::New { year: 2022, day: 1 }, parse(&["new", "2022/1"]).unwrap().command);
+        assert!(parse(&["new", "x/1"]).is_err());
+        assert!(parse(&["new"]).is_err());
+        assert!(parse(&["new", "26"]).is_err());
+        assert!(parse(&["new", "x"]).is_err())
+    }
+}
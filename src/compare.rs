@@ -0,0 +1,173 @@
+//! Cross-checks the variants of a part against its default solver, see
+//! `aoc2020 compare`.
+
+use crate::day::VariantRun;
+use crate::registry::Task;
+use crate::runner;
+use crate::runner::RunError;
+use crate::runner::EXIT_PANIC;
+
+/// Exit code bit set when the variants of a part disagree.
+pub static EXIT_MISMATCH: i32 = 8;
+
+/// Every solver of a task run on the same input.
+#[derive(Debug)]
+pub struct Comparison {
+    pub task_id: String,
+    /// The default solver comes first.
+    pub runs: Vec<VariantRun>,
+}
+
+impl Comparison {
+    /// Whether the solvers that did not panic gave different answers.
+    pub fn disagrees(&self) -> bool {
+        let mut answers = self.runs.iter().filter_map(|run| run.answer.as_ref().ok());
+        match answers.next() {
+            Some(first) => answers.any(|answer| answer != first),
+            None => false,
+        }
+    }
+
+    /// One line naming every solver and its answer, for reporting a mismatch.
+    pub fn describe(&self) -> String {
+        let answers: Vec<String> = self
+            .runs
+            .iter()
+            .map(|run| match &run.answer {
+                Ok(answer) => format!("{} {}", run.name, answer.to_string().replace('\n', "/")),
+                Err(message) => format!("{} PANIC {}", run.name, message),
+            })
+            .collect();
+        format!("{}: {}", self.task_id, answers.join(", "))
+    }
+}
+
+/// Parses the input of `task`, or the file at `input_path`, and runs the
+/// default solver and every variant of the part on it.
+pub fn compare_task(task: &Task, input_path: Option<&str>) -> Result<Comparison, RunError> {
+    let raw = runner::read_task_input(task, input_path)?;
+    let runs = (task.compare)(&raw, task.part)
+        .map_err(|err| RunError::input(err, input_path.unwrap_or(&task.data_path)))?;
+    Ok(Comparison {
+        task_id: task.id.to_string(),
+        runs,
+    })
+}
+
+/// Renders one row per solver with its answer and time, marking the parts
+/// whose solvers disagree.
+pub fn format_comparisons(comparisons: &[Comparison]) -> String {
+    let rows: Vec<[String; 4]> = comparisons
+        .iter()
+        .flat_map(|comparison| {
+            let verdict = if comparison.disagrees() { "MISMATCH" } else { "" };
+            comparison.runs.iter().map(move |run| {
+                let answer = match &run.answer {
+                    Ok(answer) => answer.to_string().replace('\n', "/"),
+                    Err(message) => format!("PANIC {}", message),
+                };
+                [
+                    comparison.task_id.to_string(),
+                    run.name.to_string(),
+                    answer,
+                    format!("{:<10}  {}", runner::format_duration(run.duration), verdict),
+                ]
+            })
+        })
+        .collect();
+    let header = [
+        String::from("task_id"),
+        String::from("variant"),
+        String::from("answer"),
+        String::from("time"),
+    ];
+    let width = |column: usize| {
+        rows.iter()
+            .chain([&header])
+            .map(|row| row[column].len())
+            .max()
+            .unwrap()
+    };
+    let widths = [width(0), width(1), width(2)];
+    let mut table = String::new();
+    for row in [&header].into_iter().chain(&rows) {
+        let line = format!(
+            "{:<w0$}  {:<w1$}  {:<w2$}  {}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2]
+        );
+        table.push_str(line.trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+/// [`EXIT_MISMATCH`] for disagreeing solvers combined with
+/// [`runner::EXIT_PANIC`] for panicking ones, zero when all agree.
+pub fn exit_code(comparisons: &[Comparison]) -> i32 {
+    comparisons.iter().fold(0, |code, comparison| {
+        let mismatch = if comparison.disagrees() { EXIT_MISMATCH } else { 0 };
+        let panic = if comparison.runs.iter().any(|run| run.answer.is_err()) { EXIT_PANIC } else { 0 };
+        code | mismatch | panic
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::answer::Answer;
+
+    fn comparison(answers: &[Result<i64, &str>]) -> Comparison {
+        let names = ["default", "fast", "naive"];
+        Comparison {
+            task_id: String::from("2021/7/2"),
+            runs: answers
+                .iter()
+                .zip(names)
+                .map(|(answer, name)| VariantRun {
+                    name,
+                    answer: answer.map(Answer::Int).map_err(|message| message.to_string()),
+                    duration: Duration::from_millis(1),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_disagrees() {
+        assert!(!comparison(&[Ok(168), Ok(168)]).disagrees());
+        assert!(comparison(&[Ok(168), Ok(170)]).disagrees());
+        assert!(!comparison(&[Ok(168), Err("boom")]).disagrees());
+        assert!(!comparison(&[Err("boom"), Err("boom")]).disagrees());
+    }
+
+    #[test]
+    fn test_exit_code() {
+        assert_eq!(0, exit_code(&[comparison(&[Ok(168), Ok(168)])]));
+        assert_eq!(EXIT_MISMATCH, exit_code(&[comparison(&[Ok(168), Ok(170)])]));
+        assert_eq!(
+            EXIT_MISMATCH | EXIT_PANIC,
+            exit_code(&[comparison(&[Ok(168), Err("boom")]), comparison(&[Ok(1), Ok(2)])])
+        );
+    }
+
+    #[test]
+    fn test_format_comparisons() {
+        let comparisons = [comparison(&[Ok(168), Ok(170), Err("boom")])];
+        let expected = "\
+task_id   variant  answer      time
+2021/7/2  default  168         1.000ms     MISMATCH
+2021/7/2  fast     170         1.000ms     MISMATCH
+2021/7/2  naive    PANIC boom  1.000ms     MISMATCH
+";
+        assert_eq!(expected, format_comparisons(&comparisons));
+        assert_eq!("2021/7/2: default 168, fast 170, naive PANIC boom", comparisons[0].describe());
+    }
+}
@@ -0,0 +1,242 @@
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::answer::Answer;
+use crate::input::InputError;
+use crate::memory;
+use crate::memory::AllocStats;
+use crate::rng::Rng;
+use crate::runner;
+
+/// A puzzle day, its input is parsed once and shared by both parts.
+pub trait Day {
+    type Input;
+
+    /// Number of parts solved so far.
+    const PARTS: u32 = 2;
+
+    /// Usage of the day-specific REPL commands handled by [`Day::inspect`],
+    /// e.g. `step <n>`.
+    const COMMANDS: &'static [&'static str] = &[];
+
+    fn parse(raw: &str) -> Result<Self::Input, InputError>;
+    fn part1(input: &Self::Input) -> Answer;
+    fn part2(input: &Self::Input) -> Answer;
+
+    /// Runs a day-specific REPL `command` with whitespace separated `args`,
+    /// returns `None` for commands the day does not know.
+    fn inspect(_input: &Self::Input, _command: &str, _args: &[&str]) -> Option<Result<String, String>> {
+        None
+    }
+
+    /// Alternative solvers of the parts, checked against `part1` and `part2`
+    /// by `aoc2020 compare`.
+    fn variants() -> Vec<Variant<Self::Input>> {
+        Vec::new()
+    }
+
+    /// A random puzzle input whose size grows with `size`, e.g. the number
+    /// of lines or the side of a grid, `None` if the day has no generator.
+    fn generate(_rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+}
+
+/// A named alternative solver of one part of a day.
+pub struct Variant<I> {
+    pub part: u32,
+    pub name: &'static str,
+    pub solve: fn(&I) -> Answer,
+}
+
+/// Name [`compare`] reports `part1` and `part2` under.
+pub static DEFAULT_VARIANT: &str = "default";
+
+fn solve<D: Day>(input: &D::Input, part: u32) -> Answer {
+    match part {
+        1 => D::part1(input),
+        2 => D::part2(input),
+        _ => panic!("Invalid part: {}", part),
+    }
+}
+
+/// Reported by [`run`] as soon as each phase is done.
+#[derive(Debug)]
+pub enum Progress {
+    Parsed(Duration),
+    /// Answer and solve time of the next requested part, and its allocations
+    /// when counting them is enabled.
+    Solved(Answer, Duration, Option<AllocStats>),
+}
+
+/// [`run`] for a specific day, which is what the registry stores.
+pub type DayFn = fn(raw: &str, parts: &[u32], progress: &mut dyn FnMut(Progress)) -> Result<(), InputError>;
+
+#[derive(Debug)]
+pub struct DayRun {
+    pub parse_duration: Duration,
+    /// Answer, solve time and allocations of each requested part, in the
+    /// requested order.
+    pub parts: Vec<(Answer, Duration, Option<AllocStats>)>,
+}
+
+/// Parses `raw` once and solves the requested `parts` on it, reporting each
+/// step to `progress`.
+///
+/// Like its time, the allocations of parsing count towards the first part.
+pub fn run<D: Day>(raw: &str, parts: &[u32], progress: &mut dyn FnMut(Progress)) -> Result<(), InputError> {
+    memory::reset();
+    let start = Instant::now();
+    let input = D::parse(raw)?;
+    progress(Progress::Parsed(start.elapsed()));
+
+    for (idx, part) in parts.iter().enumerate() {
+        if idx > 0 {
+            memory::reset();
+        }
+        let start = Instant::now();
+        let answer = solve::<D>(&input, *part);
+        let duration = start.elapsed();
+        progress(Progress::Solved(answer, duration, memory::stats()));
+    }
+    Ok(())
+}
+
+/// A day's parsed input, kept around by the REPL.
+pub trait Session {
+    fn solve(&self, part: u32) -> Answer;
+    fn inspect(&self, command: &str, args: &[&str]) -> Option<Result<String, String>>;
+    fn commands(&self) -> &'static [&'static str];
+}
+
+struct Parsed<D: Day> {
+    input: D::Input,
+}
+
+impl<D: Day> Session for Parsed<D> {
+    fn solve(&self, part: u32) -> Answer {
+        solve::<D>(&self.input, part)
+    }
+
+    fn inspect(&self, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        D::inspect(&self.input, command, args)
+    }
+
+    fn commands(&self) -> &'static [&'static str] {
+        D::COMMANDS
+    }
+}
+
+/// [`load`] for a specific day, stored next to [`DayFn`] in the registry.
+pub type LoadFn = fn(raw: &str) -> Result<Box<dyn Session>, InputError>;
+
+/// Parses `raw` into a [`Session`] of `D`.
+pub fn load<D: Day + 'static>(raw: &str) -> Result<Box<dyn Session>, InputError> {
+    Ok(Box::new(Parsed::<D> { input: D::parse(raw)? }))
+}
+
+/// Answer of one solver in [`compare`], or the message it panicked with.
+#[derive(Debug)]
+pub struct VariantRun {
+    pub name: &'static str,
+    pub answer: Result<Answer, String>,
+    pub duration: Duration,
+}
+
+/// [`compare`] for a specific day, stored next to [`DayFn`] in the registry.
+pub type CompareFn = fn(raw: &str, part: u32) -> Result<Vec<VariantRun>, InputError>;
+
+fn timed(name: &'static str, solve: impl FnOnce() -> Answer) -> VariantRun {
+    let start = Instant::now();
+    let answer = panic::catch_unwind(AssertUnwindSafe(solve)).map_err(|payload| runner::panic_message(payload.as_ref()));
+    VariantRun {
+        name,
+        answer,
+        duration: start.elapsed(),
+    }
+}
+
+/// Parses `raw` once and solves `part` with the default solver and then with
+/// each of its variants, catching panics.
+pub fn compare<D: Day>(raw: &str, part: u32) -> Result<Vec<VariantRun>, InputError> {
+    let input = D::parse(raw)?;
+    let mut runs = vec![timed(DEFAULT_VARIANT, || solve::<D>(&input, part))];
+    for variant in D::variants().into_iter().filter(|variant| variant.part == part) {
+        runs.push(timed(variant.name, || (variant.solve)(&input)));
+    }
+    Ok(runs)
+}
+
+/// [`generate`] for a specific day, stored next to [`DayFn`] in the registry.
+pub type GenerateFn = fn(seed: u64, size: usize) -> Option<String>;
+
+/// The puzzle [`Day::generate`] builds from `seed`, the same for every call.
+pub fn generate<D: Day>(seed: u64, size: usize) -> Option<String> {
+    D::generate(&mut Rng::new(seed), size)
+}
+
+/// Runs `day` to completion and collects everything it reported.
+pub fn collect(day: DayFn, raw: &str, parts: &[u32]) -> Result<DayRun, InputError> {
+    let mut run = DayRun {
+        parse_duration: Duration::ZERO,
+        parts: Vec::new(),
+    };
+    day(raw, parts, &mut |progress| match progress {
+        Progress::Parsed(duration) => run.parse_duration = duration,
+        Progress::Solved(answer, duration, alloc) => run.parts.push((answer, duration, alloc)),
+    })?;
+    Ok(run)
+}
+
+/// Reads the file at `path` and solves a single part of it.
+#[cfg(test)]
+pub fn solve_file<D: Day>(path: &str, part: u32) -> Answer {
+    let path = crate::input::data_file(&crate::input::data_dir(None), path);
+    let raw = std::fs::read_to_string(&path).unwrap_or_else(|err| panic!("Failed to read {}: {}", path, err));
+    collect(run::<D>, &raw, &[part]).unwrap().parts.remove(0).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Sum;
+    use crate::test_support::INPUT;
+
+    #[test]
+    fn test_run_parses_once_for_all_parts() {
+        let run = collect(run::<Sum>, INPUT, &[2, 1]).unwrap();
+        let answers: Vec<Answer> = run.parts.into_iter().map(|(answer, _, _)| answer).collect();
+        assert_eq!(vec![Answer::Int(24), Answer::Int(9)], answers)
+    }
+
+    #[test]
+    fn test_load_session() {
+        let session = load::<Sum>(INPUT).unwrap();
+        assert_eq!(Answer::Int(9), session.solve(1));
+        assert_eq!(None, session.inspect("step", &["10"]));
+        assert!(load::<Sum>("x\n").is_err());
+    }
+
+    #[test]
+    fn test_compare_runs_every_variant() {
+        let runs = compare::<Sum>(INPUT, 1).unwrap();
+        let answers: Vec<(&str, Result<Answer, String>)> = runs.into_iter().map(|run| (run.name, run.answer)).collect();
+        assert_eq!(
+            vec![
+                (DEFAULT_VARIANT, Ok(Answer::Int(9))),
+                ("reversed", Ok(Answer::Int(9))),
+                ("broken", Err(String::from("broken"))),
+            ],
+            answers
+        );
+        assert_eq!(1, compare::<Sum>("2\n", 2).unwrap().len());
+    }
+
+    #[test]
+    fn test_run_reports_input_error() {
+        let err = collect(run::<Sum>, "2\nthree\n", &[1]).unwrap_err();
+        assert_eq!((2, 1), (err.line, err.column));
+    }
+}
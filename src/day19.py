@@ -4,7 +4,7 @@ import itertools
 
 
 def read_input_lines(filename: str) -> list[str]:
-    with open("data/" + filename) as f:
+    with open("data/2021/" + filename) as f:
         return f.readlines()
 
 
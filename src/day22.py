@@ -21,7 +21,7 @@ class Step:
 
 
 def read_input_lines(filename: str) -> list[str]:
-    with open("data/" + filename) as f:
+    with open("data/2021/" + filename) as f:
         return f.readlines()
 
 
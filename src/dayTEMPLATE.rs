@@ -1,58 +1,64 @@
-use std::cell::Cell;
-use std::fs::File;
-use std::io::BufRead;
-use std::io::BufReader;
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::input::InputError;
 
-static DATA_FILEPATH: &str = "data/dayTEMPLATE.txt";
+pub static DATA_FILEPATH: &str = "YEARTEMPLATE/dayTEMPLATE.txt";
 
+pub type Input = Vec<String>;
 
-fn read_data(reader: BufReader<&File>) -> Vec<String> {
-    let data: Vec<String> = Vec::new();
-    data
+pub fn parse(raw: &str) -> Result<Input, InputError> {
+    Ok(raw.lines().map(|line| line.to_string()).collect())
 }
 
-pub fn task1_run(path: &str) -> i64 {
-    let file = File::open(path).unwrap();
-    let data = read_data(BufReader::new(&file));
+pub fn part1(_data: &Input) -> i64 {
     0
 }
 
-pub fn task2_run(path: &str) -> i64 {
-    let file = File::open(path).unwrap();
-    let data = read_data(BufReader::new(&file));
+pub fn part2(_data: &Input) -> i64 {
     0
 }
 
-pub fn task1() -> i64 {
-    task1_run(DATA_FILEPATH)
-}
+pub struct DayTEMPLATE;
+
+impl Day for DayTEMPLATE {
+    type Input = Input;
+
+    fn parse(raw: &str) -> Result<Input, InputError> {
+        parse(raw)
+    }
 
-pub fn task2() -> i64 {
-    task2_run(DATA_FILEPATH)
+    fn part1(input: &Input) -> Answer {
+        Answer::from(part1(input))
+    }
+
+    fn part2(input: &Input) -> Answer {
+        Answer::from(part2(input))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    static TEST_DATA_FILEPATH: &str = "data/dayTEMPLATE_test.txt";
+    use crate::day::solve_file;
+    static TEST_DATA_FILEPATH: &str = "YEARTEMPLATE/dayTEMPLATE_test.txt";
 
     #[test]
     fn task1_test_data() {
-        assert_eq!(0, task1_run(TEST_DATA_FILEPATH))
+        assert_eq!(Answer::Int(0), solve_file::<DayTEMPLATE>(TEST_DATA_FILEPATH, 1))
     }
 
     #[test]
     fn task1() {
-        assert_eq!(0, task1_run(DATA_FILEPATH));
+        assert_eq!(Answer::Int(0), solve_file::<DayTEMPLATE>(DATA_FILEPATH, 1));
     }
 
     #[test]
     fn task2_test_data() {
-        assert_eq!(0, task2_run(TEST_DATA_FILEPATH))
+        assert_eq!(Answer::Int(0), solve_file::<DayTEMPLATE>(TEST_DATA_FILEPATH, 2))
     }
 
     #[test]
     fn task2() {
-        assert_eq!(0, task2_run(DATA_FILEPATH))
+        assert_eq!(Answer::Int(0), solve_file::<DayTEMPLATE>(DATA_FILEPATH, 2))
     }
 }
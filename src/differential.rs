@@ -0,0 +1,119 @@
+//! Differential testing: every solver of a day, variants included, against a
+//! brute-force reference on puzzles from [`Day::generate`].
+//!
+//! Each day's test runs a few hundred to a few thousand cases to keep
+//! `cargo test` fast. [`CASES_ENV`] runs the same number of cases for every
+//! day instead, e.g. `AOC_DIFFERENTIAL_CASES=5000 cargo test --release`.
+
+use std::env;
+use std::fmt;
+
+use crate::answer::Answer;
+use crate::day;
+use crate::day::Day;
+
+/// Environment variable overriding the number of cases of every day.
+pub static CASES_ENV: &str = "AOC_DIFFERENTIAL_CASES";
+
+/// `cases`, unless `overridden` by [`CASES_ENV`].
+fn case_count(cases: u64, overridden: Option<&str>) -> u64 {
+    match overridden {
+        Some(value) => value
+            .parse()
+            .unwrap_or_else(|_| panic!("Expected {} to be a number of cases, got {}", CASES_ENV, value)),
+        None => cases,
+    }
+}
+
+/// Straightforward solver of `part`, trusted to be right but allowed to be
+/// slow.
+pub type Reference<I> = fn(input: &I, part: u32) -> Answer;
+
+/// A solver disagreeing with the reference on a generated puzzle.
+#[derive(Debug)]
+pub struct Mismatch {
+    pub seed: u64,
+    pub size: usize,
+    pub part: u32,
+    pub solver: &'static str,
+    pub expected: Answer,
+    /// The answer, or the message the solver panicked with.
+    pub actual: Result<Answer, String>,
+    pub raw: String,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let actual = match &self.actual {
+            Ok(answer) => answer.to_string(),
+            Err(message) => format!("PANIC {}", message),
+        };
+        write!(
+            f,
+            "part {} solver {} on seed {} of size {}: expected {}, got {}\n{}",
+            self.part, self.solver, self.seed, self.size, self.expected, actual, self.raw
+        )
+    }
+}
+
+/// Solves the puzzles of seeds `0..cases`, whose sizes cycle through
+/// `1..=max_size`, with every solver of `D` and returns the first mismatch
+/// of each solver. [`CASES_ENV`] overrides `cases`.
+pub fn mismatches<D: Day>(cases: u64, max_size: usize, reference: Reference<D::Input>) -> Vec<Mismatch> {
+    let cases = case_count(cases, env::var(CASES_ENV).ok().as_deref());
+    let mut found: Vec<Mismatch> = Vec::new();
+    for seed in 0..cases {
+        let size = 1 + seed as usize % max_size;
+        let raw = day::generate::<D>(seed, size).expect("The day has no generator");
+        let input = D::parse(&raw).unwrap_or_else(|err| panic!("Seed {} generated an invalid input: {}\n{}", seed, err, raw));
+        for part in 1..=D::PARTS {
+            let expected = reference(&input, part);
+            for run in day::compare::<D>(&raw, part).unwrap() {
+                let known = found.iter().any(|mismatch| mismatch.part == part && mismatch.solver == run.name);
+                if run.answer.as_ref() != Ok(&expected) && !known {
+                    found.push(Mismatch {
+                        seed,
+                        size,
+                        part,
+                        solver: run.name,
+                        expected: expected.clone(),
+                        actual: run.answer,
+                        raw: raw.clone(),
+                    });
+                }
+            }
+        }
+    }
+    found
+}
+
+/// Panics with every mismatch [`mismatches`] finds.
+pub fn check<D: Day>(cases: u64, max_size: usize, reference: Reference<D::Input>) {
+    let found = mismatches::<D>(cases, max_size, reference);
+    let report: Vec<String> = found.iter().map(Mismatch::to_string).collect();
+    assert!(found.is_empty(), "{}", report.join("\n"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Sum;
+
+    #[test]
+    fn test_case_count() {
+        assert_eq!(200, case_count(200, None));
+        assert_eq!(5000, case_count(200, Some("5000")));
+    }
+
+    #[test]
+    fn test_mismatches() {
+        let found = mismatches::<Sum>(20, 4, |input, part| match part {
+            1 => Answer::Int(input.iter().sum()),
+            _ => Answer::Int(input.iter().product()),
+        });
+        assert_eq!(1, found.len());
+        assert_eq!((1, "broken"), (found[0].part, found[0].solver));
+        assert_eq!(Err(String::from("broken")), found[0].actual);
+        assert_eq!(found[0].raw, day::generate::<Sum>(found[0].seed, found[0].size).unwrap());
+    }
+}
@@ -0,0 +1,269 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Input path that stands for the standard input.
+pub static STDIN: &str = "-";
+
+/// Environment variable overriding the data directory.
+pub static DATA_DIR_ENV: &str = "AOC_DATA_DIR";
+
+/// Data directory of this crate, independent of the working directory.
+pub static DEFAULT_DATA_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/data");
+
+/// Picks the data directory from `--data-dir`, then `AOC_DATA_DIR`, then
+/// [`DEFAULT_DATA_DIR`].
+pub fn data_dir(flag: Option<&str>) -> PathBuf {
+    flag.map(PathBuf::from)
+        .or_else(|| env::var_os(DATA_DIR_ENV).filter(|dir| !dir.is_empty()).map(PathBuf::from))
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_DATA_DIR))
+}
+
+/// Resolves a data file like `day9.txt` against `data_dir`, absolute paths and
+/// `-` are kept as they are.
+pub fn data_file(data_dir: &Path, file: &str) -> String {
+    if file == STDIN {
+        return file.to_string();
+    }
+    data_dir.join(file).to_string_lossy().into_owned()
+}
+
+/// Data files compiled into the binary, generated by `build.rs`.
+#[cfg(feature = "embedded-inputs")]
+static EMBEDDED: &[(&str, &str)] = include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
+
+/// Returns the copy of a data file like `day9.txt` compiled into the binary,
+/// only available with the `embedded-inputs` feature.
+#[cfg(feature = "embedded-inputs")]
+pub fn embedded(file: &str) -> Option<&'static str> {
+    EMBEDDED.iter().find(|(name, _)| *name == file).map(|(_, raw)| *raw)
+}
+
+#[cfg(not(feature = "embedded-inputs"))]
+pub fn embedded(_file: &str) -> Option<&'static str> {
+    None
+}
+
+/// Reads the whole task input, `-` reads from stdin.
+pub fn read_to_string(path: &str) -> io::Result<String> {
+    if path == STDIN {
+        io::read_to_string(io::stdin())
+    } else {
+        fs::read_to_string(path)
+    }
+}
+
+/// Malformed input, pointing at the offending text of a line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputError {
+    /// Only known once the runner attaches it, see [`InputError::with_file`].
+    pub file: Option<String>,
+    /// Starts at 1.
+    pub line: usize,
+    /// Starts at 1, counted in characters.
+    pub column: usize,
+    pub line_text: String,
+    pub expected: String,
+    /// Either the quoted offending text or a description like `end of line`.
+    pub found: String,
+    /// Number of characters to underline.
+    pub width: usize,
+}
+
+impl InputError {
+    pub fn with_file(mut self, file: &str) -> Self {
+        self.file = Some(file.to_string());
+        self
+    }
+}
+
+/// Renders the error like a compiler diagnostic:
+///
+/// ```text
+/// error: expected a number, found `x`
+///  --> data/day2.txt:3:9
+///   |
+/// 3 | forward x
+///   |         ^
+/// ```
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "error: expected {}, found {}", self.expected, self.found)?;
+        writeln!(
+            f,
+            "{}--> {}:{}:{}",
+            gutter,
+            self.file.as_deref().unwrap_or("<input>"),
+            self.line,
+            self.column
+        )?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.line_text)?;
+        write!(
+            f,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(self.width.max(1))
+        )
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// A line of the raw input together with its line number.
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    /// Starts at 1.
+    pub number: usize,
+    pub text: &'a str,
+}
+
+/// Splits the raw input into numbered lines.
+pub fn lines(raw: &str) -> impl Iterator<Item = Line<'_>> {
+    raw.lines().enumerate().map(|(idx, text)| Line { number: idx + 1, text })
+}
+
+/// Error for input that ends before `expected` was found.
+pub fn end_of_input(raw: &str, expected: &str) -> InputError {
+    Line {
+        number: raw.lines().count() + 1,
+        text: "",
+    }
+    .error_at(0, 0, expected, "end of input")
+}
+
+impl<'a> Line<'a> {
+    fn error_at(&self, byte_offset: usize, width: usize, expected: &str, found: &str) -> InputError {
+        InputError {
+            file: None,
+            line: self.number,
+            column: self.text[..byte_offset].chars().count() + 1,
+            line_text: self.text.to_string(),
+            expected: expected.to_string(),
+            found: found.to_string(),
+            width,
+        }
+    }
+
+    /// Error pointing at `found`, which should be a slice of this line.
+    ///
+    /// Anything else is reported at the start of the line.
+    pub fn error(&self, found: &str, expected: &str) -> InputError {
+        let start = self.text.as_ptr() as usize;
+        let offset = (found.as_ptr() as usize).wrapping_sub(start);
+        let offset = match offset.checked_add(found.len()) {
+            Some(end) if end <= self.text.len() && self.text.is_char_boundary(offset) => offset,
+            _ => 0,
+        };
+        let found_text = if found.is_empty() {
+            String::from("nothing")
+        } else {
+            format!("`{}`", found)
+        };
+        self.error_at(offset, found.chars().count(), expected, &found_text)
+    }
+
+    /// Error pointing just past the end of the line.
+    pub fn missing(&self, expected: &str) -> InputError {
+        self.error_at(self.text.len(), 0, expected, "end of line")
+    }
+
+    /// Unwraps a field split off this line, `None` means the line ended early.
+    pub fn field(&self, field: Option<&'a str>, expected: &str) -> Result<&'a str, InputError> {
+        field.ok_or_else(|| self.missing(expected))
+    }
+
+    /// Parses `field`, a slice of this line.
+    pub fn parse<T: FromStr>(&self, field: &str, expected: &str) -> Result<T, InputError> {
+        field.parse().map_err(|_| self.error(field, expected))
+    }
+
+    /// Errors at the first character of the line that is not `valid`.
+    pub fn check_chars(&self, valid: impl Fn(char) -> bool, expected: &str) -> Result<(), InputError> {
+        match self.text.char_indices().find(|(_, c)| !valid(*c)) {
+            Some((idx, c)) => Err(self.error(&self.text[idx..idx + c.len_utf8()], expected)),
+            None => Ok(()),
+        }
+    }
+
+    /// Reads a line made only of decimal digits.
+    pub fn digits(&self) -> Result<Vec<u32>, InputError> {
+        self.check_chars(|c| c.is_ascii_digit(), "a digit")?;
+        Ok(self.text.chars().filter_map(|c| c.to_digit(10)).collect())
+    }
+
+    /// Errors unless the whole line was consumed, `rest` being what is left.
+    pub fn end(&self, rest: Option<&str>) -> Result<(), InputError> {
+        match rest {
+            Some(rest) => Err(self.error(rest, "end of line")),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_data_file(file: &str) -> String {
+        data_file(Path::new(DEFAULT_DATA_DIR), file)
+    }
+
+    #[test]
+    fn test_data_dir() {
+        assert_eq!(PathBuf::from("/tmp/aoc"), data_dir(Some("/tmp/aoc")));
+        assert_eq!("/tmp/aoc/day9.txt", data_file(Path::new("/tmp/aoc"), "day9.txt"));
+        assert_eq!("/other/day9.txt", data_file(Path::new("/tmp/aoc"), "/other/day9.txt"));
+        assert_eq!("-", data_file(Path::new("/tmp/aoc"), "-"));
+    }
+
+    #[test]
+    fn test_embedded() {
+        let expected = cfg!(feature = "embedded-inputs")
+            .then(|| read_to_string(&default_data_file("2021/day2_test.txt")).unwrap());
+        assert_eq!(expected.as_deref(), embedded("2021/day2_test.txt"));
+        assert_eq!(None, embedded("answers.json"));
+    }
+
+    #[test]
+    fn test_read_to_string() {
+        assert_eq!(2000, read_to_string(&default_data_file("2021/day1.txt")).unwrap().lines().count());
+        assert!(read_to_string(&default_data_file("missing.txt")).is_err())
+    }
+
+    #[test]
+    fn test_input_error_display() {
+        let line = lines("up 3\nforward x2").nth(1).unwrap();
+        let err = line.parse::<i64>(&line.text[8..], "a number").unwrap_err();
+        let expected = "\
+error: expected a number, found `x2`
+ --> data/day2.txt:2:9
+  |
+2 | forward x2
+  |         ^^";
+        assert_eq!(expected, err.with_file("data/day2.txt").to_string());
+    }
+
+    #[test]
+    fn test_line_errors() {
+        let line = lines("a-b-c").next().unwrap();
+        let mut split = line.text.split('-');
+        assert_eq!("a", line.field(split.next(), "a cave").unwrap());
+        split.next();
+        assert_eq!(5, line.end(split.next()).unwrap_err().column);
+        assert_eq!(6, line.missing("a cave").column);
+        assert_eq!("end of line", line.missing("a cave").found);
+        assert_eq!(1, line.error("not a slice", "a cave").column);
+        assert_eq!(4, end_of_input("a\nb\nc\n", "a cave").line);
+
+        let line = lines("12a4").next().unwrap();
+        assert_eq!(3, line.digits().unwrap_err().column);
+        assert_eq!(vec![1, 2, 3], lines("123").next().unwrap().digits().unwrap());
+    }
+}
@@ -0,0 +1,65 @@
+//! Puzzle solutions plus the runner around them.
+//!
+//! Puzzles are grouped per year, e.g. `y2021::day5`. Every `dayN` module
+//! exposes its parsed `Input` type, a `parse` function building it from the raw
+//! input and the `part1` and `part2` solvers, all tied together by its
+//! implementation of [`day::Day`].
+
+use std::path::Path;
+
+use registry::DayEntry;
+use registry::TaskRegistry;
+
+pub mod answer;
+pub mod bench;
+pub mod cache;
+pub mod cli;
+pub mod compare;
+pub mod day;
+#[cfg(test)]
+mod differential;
+mod hex;
+pub mod input;
+pub mod json;
+pub mod log;
+pub mod memory;
+#[cfg(test)]
+mod mutation;
+pub mod python;
+pub mod registry;
+pub mod repl;
+pub mod rng;
+pub mod runner;
+pub mod scaffold;
+pub mod serve;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod verify;
+
+#[cfg(test)]
+#[global_allocator]
+static ALLOCATOR: memory::CountingAllocator = memory::CountingAllocator;
+
+/// Declares the day modules of a year's module, which also defines `YEAR`,
+/// and lists them in `days()` for the registry. Each module exports its
+/// `DATA_FILEPATH`, relative to the data directory (see [`input::data_dir`]),
+/// and its `Day` implementation.
+macro_rules! days {
+    ($($(#[$attr:meta])* $day:ident::$ty:ident),* $(,)?) => {
+        $($(#[$attr])* pub mod $day;)*
+
+        pub fn days() -> Vec<$crate::registry::DayEntry> {
+            vec![$($crate::registry::DayEntry::of::<$day::$ty>(YEAR, stringify!($day), $day::DATA_FILEPATH)),*]
+        }
+    };
+}
+
+pub mod y2021;
+
+/// The `days()` of every year module, adding a year is one line here.
+static YEARS: &[fn() -> Vec<DayEntry>] = &[y2021::days];
+
+pub fn tasks_registry(data_dir: &Path) -> TaskRegistry {
+    let days: Vec<DayEntry> = YEARS.iter().flat_map(|days| days()).collect();
+    TaskRegistry::from_days(data_dir, &days)
+}
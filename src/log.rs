@@ -0,0 +1,68 @@
+//! Diagnostics on stderr, shown depending on the `-v`/`-vv` verbosity so the
+//! normal output is only the answers.
+
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// Shown with `-v`, e.g. which input a task reads.
+    Info = 1,
+    /// Shown with `-vv`, e.g. dumps of parsed inputs.
+    Debug = 2,
+}
+
+impl Level {
+    pub fn label(self) -> &'static str {
+        match self {
+            Level::Info => "info",
+            Level::Debug => "debug",
+        }
+    }
+}
+
+/// Number of `-v` flags given, messages above it are dropped.
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_verbosity(verbosity: u8) {
+    VERBOSITY.store(verbosity, Ordering::Relaxed)
+}
+
+pub fn enabled(level: Level) -> bool {
+    level as u8 <= VERBOSITY.load(Ordering::Relaxed)
+}
+
+/// Prints a message at `level` to stderr, formatting it only when enabled.
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)*) => {
+        if $crate::log::enabled($level) {
+            eprintln!("[{}] {}", $level.label(), format_args!($($arg)*))
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => { $crate::log!($crate::log::Level::Info, $($arg)*) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => { $crate::log!($crate::log::Level::Debug, $($arg)*) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels() {
+        assert!(Level::Info < Level::Debug);
+        assert!(!enabled(Level::Info));
+        set_verbosity(1);
+        assert!(enabled(Level::Info));
+        assert!(!enabled(Level::Debug));
+        set_verbosity(0);
+    }
+}
@@ -1,103 +1,382 @@
-use std::collections::HashMap;
 use std::env;
+use std::io;
+use std::net::TcpListener;
+use std::path::Path;
+use std::process;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
 
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod hex;
-
-type Task = fn() -> i64;
-
-struct TaskRegistry {
-    tasks: HashMap<String, Task>,
-    latest_task_id: String,
-}
-
-impl TaskRegistry {
-    fn new(tasks_init: &[(&str, Task)]) -> Self {
-        let mut tasks = HashMap::<String, Task>::new();
-        for (task_id, task_fn) in tasks_init {
-            tasks.insert(task_id.to_string(), *task_fn);
+use aoc2020::bench;
+use aoc2020::cache;
+use aoc2020::cache::AnswerCache;
+use aoc2020::cli::Args;
+use aoc2020::cli::Command;
+use aoc2020::cli::Format;
+use aoc2020::compare;
+use aoc2020::compare::Comparison;
+use aoc2020::info;
+use aoc2020::input;
+use aoc2020::log;
+use aoc2020::memory;
+use aoc2020::memory::CountingAllocator;
+use aoc2020::python;
+use aoc2020::python::PortRun;
+use aoc2020::registry::Task;
+use aoc2020::registry::TaskRegistry;
+use aoc2020::repl;
+use aoc2020::repl::Repl;
+use aoc2020::runner;
+use aoc2020::runner::RunError;
+use aoc2020::runner::TaskReport;
+use aoc2020::scaffold;
+use aoc2020::serve;
+use aoc2020::tasks_registry;
+use aoc2020::verify;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn main() {
+    let args = Args::parse(env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(2)
+    });
+
+    log::set_verbosity(args.verbosity);
+    if args.alloc_stats {
+        memory::enable();
+    }
+
+    let data_dir = input::data_dir(args.data_dir.as_deref());
+    let tasks_registry = tasks_registry(&data_dir);
+    // Only run and all use the cache, whose answers come without allocation
+    // counts.
+    let cache = match args.command {
+        Command::Run { .. } | Command::All { .. } if !args.no_cache && !args.alloc_stats => {
+            cache::cache_file().map(|path| load_cache(&path))
         }
-        let latest_task_id = tasks_init
-            .last()
-            .expect("tasks_init should have at least one task")
-            .0
-            .to_string();
-        TaskRegistry {
-            tasks,
-            latest_task_id,
+        _ => None,
+    };
+    let cache = cache.as_ref();
+
+    match args.command {
+        Command::Run { task_id } => {
+            let task_id = task_id.unwrap_or_else(|| tasks_registry.latest_task_id().to_string());
+            let tasks = tasks_registry.select(&task_id).unwrap_or_else(exit_with_message);
+            match args.format {
+                Format::Text => run_text(&tasks, args.input.as_deref(), args.timeout, cache),
+                Format::Json => run_json(&tasks, args.input.as_deref(), 1, args.timeout, cache),
+            }
         }
+        Command::All { jobs } => match args.format {
+            Format::Text => run_all(&tasks_registry, jobs, args.timeout, cache),
+            Format::Json => {
+                let tasks: Vec<&Task> = tasks_registry.iter().collect();
+                run_json(&tasks, None, jobs, args.timeout, cache)
+            }
+        },
+        Command::Bench {
+            task_id,
+            warmup,
+            runs,
+            save,
+        } => run_bench(&tasks_registry, &task_id, args.input.as_deref(), warmup, runs, save),
+        Command::Verify { answers } => run_verify(
+            &tasks_registry,
+            &answers.unwrap_or_else(|| input::data_file(&data_dir, verify::ANSWERS_FILEPATH)),
+        ),
+        Command::Compare { task_id } => run_compare(&tasks_registry, task_id.as_deref(), args.input.as_deref()),
+        Command::Generate { day, seed, size } => run_generate(&tasks_registry, &day, seed, size),
+        Command::Python { task_id } => run_python(
+            &tasks_registry,
+            &data_dir,
+            task_id.as_deref(),
+            args.input.as_deref(),
+            args.format,
+            args.timeout,
+        ),
+        Command::Repl { day } => run_repl(&tasks_registry, &day, args.input.as_deref()),
+        Command::Serve { port } => run_serve(&tasks_registry, port, args.timeout),
+        Command::New { year, day } => run_new(year, day),
+    }
+}
+
+/// Reports a task that could not run, e.g. with a diagnostic pointing at
+/// malformed input.
+fn exit_with<T>(err: RunError) -> T {
+    eprintln!("{}", err);
+    process::exit(2)
+}
+
+/// Like [`exit_with`] for errors that are only a message.
+fn exit_with_message<T>(err: String) -> T {
+    eprintln!("{}", err);
+    process::exit(2)
+}
+
+/// Reads the cache at `path`, starting over when it is unreadable.
+fn load_cache(path: &Path) -> AnswerCache {
+    AnswerCache::load(path).unwrap_or_else(|err| {
+        eprintln!("{}, ignoring the cache", err);
+        AnswerCache::default()
+    })
+}
+
+/// Writes back `cache` with the answers of the run, failing to do so only
+/// costs the next run its cache hits. Nothing is written when the directory of
+/// the cache does not exist, e.g. for a binary copied to another machine.
+fn save_cache(cache: Option<&AnswerCache>) {
+    let (Some(cache), Some(path)) = (cache, cache::cache_file()) else {
+        return;
+    };
+    if !path.parent().is_some_and(Path::is_dir) {
+        info!("Not saving the cache, {} has no directory", path.display());
+        return;
+    }
+    if let Err(err) = cache.save(&path) {
+        eprintln!("Failed to save {}: {}", path.display(), err);
     }
+}
 
-    fn get(&self, task_id: &str) -> Option<&Task> {
-        self.tasks.get(task_id)
+/// Exits with [`runner::exit_code`] if any task failed.
+fn exit_on_failures(reports: &[TaskReport]) {
+    let code = runner::exit_code(reports);
+    if code != 0 {
+        process::exit(code)
     }
+}
 
-    fn latest_task_id(&self) -> &str {
-        &self.latest_task_id
+/// Runs the selected tasks, parts of the same day share the parsed input.
+fn run_text(tasks: &[&Task], input: Option<&str>, timeout: Option<Duration>, cache: Option<&AnswerCache>) {
+    let reports = runner::run_tasks(tasks, input, 1, timeout, cache).unwrap_or_else(exit_with);
+    save_cache(cache);
+    for report in &reports {
+        let cached = if report.cached { " (cached)" } else { "" };
+        println!("Running task_id: {}{}", report.task_id, cached);
+        match &report.answer {
+            Ok(answer) if answer.is_multiline() => println!("result:\n{}", answer),
+            Ok(answer) => println!("result: {}", answer),
+            Err(failure) => println!("result: {}", failure),
+        }
     }
+    exit_on_failures(&reports)
 }
 
-fn main() {
-    let tasks_registry = TaskRegistry::new(&[
-        ("day1_task1", day1::task1),
-        ("day1_task2", day1::task2),
-        ("day2_task1", day2::task1),
-        ("day2_task2", day2::task2),
-        ("day3_task1", day3::task1),
-        ("day3_task2", day3::task2),
-        ("day4_task1", day4::task1),
-        ("day4_task2", day4::task2),
-        ("day5_task1", day5::task1),
-        ("day5_task2", day5::task2),
-        ("day6_task1", day6::task1),
-        ("day6_task2", day6::task2),
-        ("day7_task1", day7::task1),
-        ("day7_task2", day7::task2),
-        ("day8_task1", day8::task1),
-        ("day8_task2", day8::task2),
-        ("day9_task1", day9::task1),
-        ("day9_task2", day9::task2),
-        ("day10_task1", day10::task1),
-        ("day10_task2", day10::task2),
-        ("day11_task1", day11::task1),
-        ("day11_task2", day11::task2),
-        ("day12_task1", day12::task1),
-        ("day12_task2", day12::task2),
-        ("day13_task1", day13::task1),
-        ("day13_task2", day13::task2),
-        ("day14_task1", day14::task1),
-        ("day14_task2", day14::task2),
-        ("day15_task1", day15::task1),
-        ("day15_task2", day15::task2),
-    ]);
-
-    let task_id = match env::args().nth(1) {
-        Some(task_id) => task_id,
-        _ => tasks_registry.latest_task_id().to_string()
-    };
+fn run_all(tasks_registry: &TaskRegistry, jobs: usize, timeout: Option<Duration>, cache: Option<&AnswerCache>) {
+    let start = Instant::now();
+    let reports = runner::run_all(tasks_registry, jobs, timeout, cache)
+        .unwrap_or_else(exit_with);
+    save_cache(cache);
+    print!("{}", runner::format_table(&reports, start.elapsed()));
+    exit_on_failures(&reports)
+}
 
-    let result = match tasks_registry.get(&task_id) {
-        Some(func) => {
-            println!("Running task_id: {}", task_id);
-            func()
+/// Prints one JSON line per task, a failing task does not stop the others.
+///
+/// Tasks run on `jobs` threads, the lines are still printed in task order.
+fn run_json(tasks: &[&Task], input: Option<&str>, jobs: usize, timeout: Option<Duration>, cache: Option<&AnswerCache>) {
+    let reports = runner::run_tasks(tasks, input, jobs, timeout, cache).unwrap_or_else(exit_with);
+    save_cache(cache);
+    for (task, report) in tasks.iter().zip(&reports) {
+        let input_path = input.unwrap_or(&task.data_path);
+        println!("{}", runner::format_json(task.key(), Some(input_path), report));
+    }
+    exit_on_failures(&reports)
+}
+
+fn run_bench(
+    tasks_registry: &TaskRegistry,
+    task_id: &str,
+    input: Option<&str>,
+    warmup: usize,
+    runs: usize,
+    save: bool,
+) {
+    let task = tasks_registry
+        .get(task_id)
+        .ok_or_else(|| format!("Invalid task_id: {}", task_id))
+        .unwrap_or_else(exit_with_message);
+    println!("Benchmarking task_id: {} ({} warmup, {} runs)", task.id, warmup, runs);
+    let result = bench::bench_task(task, input, warmup, runs)
+        .unwrap_or_else(exit_with);
+    print!("{}", bench::format_result(&result));
+
+    let mut baseline = bench::Baseline::load(bench::BASELINE_FILEPATH).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(2)
+    });
+    let mut regressed = false;
+    match baseline.get(&task.id) {
+        Some(previous) if !save && previous.input_path == result.input_path => {
+            println!("Median compared to baseline in {}:", bench::BASELINE_FILEPATH);
+            let (report, is_regression) = bench::compare(previous, &result);
+            print!("{}", report);
+            regressed = is_regression;
+        }
+        Some(previous) if !save => {
+            println!(
+                "Baseline was recorded for input {}, use --save to replace it.",
+                previous.input_path
+            );
         }
-        _ => panic!("Invalid task_id: {}", task_id),
+        _ => {
+            baseline.set(result);
+            baseline
+                .save(bench::BASELINE_FILEPATH)
+                .unwrap_or_else(|err| exit_with_message(format!("Failed to save {}: {}", bench::BASELINE_FILEPATH, err)));
+            println!("Saved baseline to {}", bench::BASELINE_FILEPATH);
+        }
+    }
+
+    if regressed {
+        process::exit(1)
+    }
+}
+
+fn run_verify(tasks_registry: &TaskRegistry, answers_path: &str) {
+    let answers = verify::load_answers(answers_path).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(2)
+    });
+
+    let mut failures = 0;
+    for expected in &answers {
+        let outcome = verify::verify_one(tasks_registry, expected);
+        if outcome != verify::Outcome::Ok {
+            failures += 1;
+        }
+        print!("{}", verify::format_outcome(expected, &outcome));
+    }
+    println!("{} passed, {} failed", answers.len() - failures, failures);
+
+    if failures > 0 {
+        process::exit(1)
+    }
+}
+
+/// Compares the variants of the tasks `task_id` selects, or of all tasks that
+/// have variants, exiting with [`compare::exit_code`].
+fn run_compare(tasks_registry: &TaskRegistry, task_id: Option<&str>, input: Option<&str>) {
+    let selected = match task_id {
+        Some(task_id) => tasks_registry.select(task_id).unwrap_or_else(|err| {
+            eprintln!("{}", err);
+            process::exit(2)
+        }),
+        None => tasks_registry.iter().collect(),
     };
+    let tasks: Vec<&Task> = selected.into_iter().filter(|task| !task.variants.is_empty()).collect();
+    if tasks.is_empty() {
+        eprintln!("No variants registered for {}", task_id.unwrap_or("any task"));
+        process::exit(2)
+    }
+    let comparisons: Vec<Comparison> = tasks
+        .iter()
+        .map(|task| compare::compare_task(task, input).unwrap_or_else(exit_with))
+        .collect();
+    print!("{}", compare::format_comparisons(&comparisons));
+    for comparison in comparisons.iter().filter(|comparison| comparison.disagrees()) {
+        eprintln!("MISMATCH {}", comparison.describe());
+    }
+    let code = compare::exit_code(&comparisons);
+    if code != 0 {
+        process::exit(code)
+    }
+}
 
-    println!("result: {}", result)
+/// Prints the input the day's generator builds, the seed goes to stderr so
+/// that a puzzle from the default seed can be built again.
+fn run_generate(tasks_registry: &TaskRegistry, day: &str, seed: Option<u64>, size: usize) {
+    let task = tasks_registry.select(day).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(2)
+    })[0];
+    let seed = seed.unwrap_or_else(|| {
+        let now = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default();
+        now.as_nanos() as u64
+    });
+    match (task.generate)(seed, size) {
+        Some(raw) => {
+            eprintln!("seed {}", seed);
+            print!("{}", raw)
+        }
+        None => {
+            eprintln!("No generator for {}/{}", task.year, task.day);
+            process::exit(2)
+        }
+    }
+}
+
+/// Runs the scripts `task_id` selects, or all of them, and the Rust tasks of
+/// the same parts on the same input, exiting with [`python::exit_code`].
+fn run_python(
+    tasks_registry: &TaskRegistry,
+    data_dir: &Path,
+    task_id: Option<&str>,
+    input: Option<&str>,
+    format: Format,
+    timeout: Option<Duration>,
+) {
+    let scripts = python::scripts(Path::new(python::SCRIPTS_DIR))
+        .unwrap_or_else(|err| exit_with_message(format!("Failed to list {}: {}", python::SCRIPTS_DIR, err)));
+    let selected = python::select(&scripts, task_id).unwrap_or_else(exit_with_message);
+
+    let start = Instant::now();
+    let mut runs: Vec<PortRun> = Vec::new();
+    for (script, part) in selected {
+        let input_path = input
+            .map(str::to_string)
+            .unwrap_or_else(|| input::data_file(data_dir, &script.data_file()));
+        let raw = runner::read_input(&input_path).unwrap_or_else(exit_with);
+        let run = python::run_part(script, part, &raw, tasks_registry, timeout).unwrap_or_else(exit_with_message);
+        if format == Format::Json {
+            println!("{}", runner::format_json(run.key, Some(&input_path), &run.python));
+        }
+        runs.push(run);
+    }
+
+    let code = python::exit_code(&runs);
+    let ported: Vec<String> = runs.iter().filter(|run| run.rust.is_some()).map(PortRun::describe).collect();
+    for run in runs.iter().filter(|run| run.disagrees()) {
+        eprintln!("MISMATCH {}", run.describe());
+    }
+    if format == Format::Text {
+        let reports: Vec<TaskReport> = runs.into_iter().map(|run| run.python).collect();
+        print!("{}", runner::format_table(&reports, start.elapsed()));
+        for line in ported {
+            println!("{}", line);
+        }
+    }
+    if code != 0 {
+        process::exit(code)
+    }
+}
+
+fn run_repl(tasks_registry: &TaskRegistry, day: &str, input: Option<&str>) {
+    let task = tasks_registry.select(day).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(2)
+    })[0];
+    let mut repl = Repl::load(task, input).unwrap_or_else(exit_with);
+    repl::run(&mut repl, io::stdin().lock(), io::stdout())
+        .unwrap_or_else(|err| panic!("Failed to run the REPL: {}", err));
+}
+
+fn run_serve(tasks_registry: &TaskRegistry, port: u16, timeout: Option<Duration>) {
+    let listener = TcpListener::bind(("127.0.0.1", port)).unwrap_or_else(|err| {
+        eprintln!("Failed to listen on port {}: {}", port, err);
+        process::exit(2)
+    });
+    println!("Listening on http://{}", listener.local_addr().unwrap());
+    serve::serve(&listener, tasks_registry, timeout, serve::MAX_SOLVERS)
+}
+
+fn run_new(year: u32, day: u32) {
+    let created = scaffold::new_day(Path::new(scaffold::ROOT), year, day).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(2)
+    });
+    for path in created {
+        println!("Wrote {}", path.display());
+    }
 }
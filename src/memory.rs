@@ -0,0 +1,178 @@
+//! Counting global allocator for per-task allocation statistics, see
+//! `--alloc-stats`.
+//!
+//! Counters are kept per thread so tasks running side by side on `--jobs`
+//! threads do not mix, and nothing is counted until [`enable`] is called.
+
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::alloc::System;
+use std::cell::Cell;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+/// Forwards to the system allocator, counting on the calling thread.
+pub struct CountingAllocator;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Copy)]
+struct Counters {
+    allocations: u64,
+    bytes: u64,
+    /// Can go negative when memory allocated before [`reset`] is freed.
+    live: i64,
+    peak: i64,
+}
+
+impl Counters {
+    const ZERO: Counters = Counters {
+        allocations: 0,
+        bytes: 0,
+        live: 0,
+        peak: 0,
+    };
+}
+
+thread_local! {
+    // No destructor, so it is safe to use from inside the allocator.
+    static COUNTERS: Cell<Counters> = const { Cell::new(Counters::ZERO) };
+}
+
+/// Allocations made by a task.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    /// Total of all allocation sizes, including memory freed again.
+    pub bytes: u64,
+    /// Most memory held at once.
+    pub peak_bytes: u64,
+}
+
+fn record(allocated: usize, freed: usize) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let _ = COUNTERS.try_with(|counters| {
+        let mut c = counters.get();
+        if allocated > 0 {
+            c.allocations += 1;
+            c.bytes += allocated as u64;
+        }
+        c.live += allocated as i64 - freed as i64;
+        c.peak = c.peak.max(c.live);
+        counters.set(c);
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(0, layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// Starts counting, only has an effect with [`CountingAllocator`] installed as
+/// the global allocator.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed)
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Restarts the counters of the calling thread from zero.
+pub fn reset() {
+    COUNTERS.with(|counters| counters.set(Counters::ZERO))
+}
+
+/// Allocations of the calling thread since the last [`reset`], `None` unless
+/// counting is enabled.
+pub fn stats() -> Option<AllocStats> {
+    if !is_enabled() {
+        return None;
+    }
+    let c = COUNTERS.with(|counters| counters.get());
+    Some(AllocStats {
+        allocations: c.allocations,
+        bytes: c.bytes,
+        peak_bytes: c.peak.max(0) as u64,
+    })
+}
+
+/// Formats a byte count with a binary unit, e.g. `1.5MiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    let units = ["KiB", "MiB", "GiB"];
+    if bytes < 1024 {
+        return format!("{}B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < units.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1}{}", value, units[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_calling_thread() {
+        enable();
+        let stats = std::thread::spawn(|| {
+            reset();
+            let mut v: Vec<u64> = Vec::with_capacity(100);
+            v.push(1);
+            drop(v);
+            let kept = vec![0u8; 10];
+            let stats = stats();
+            drop(kept);
+            stats
+        })
+        .join()
+        .unwrap();
+        assert_eq!(
+            Some(AllocStats {
+                allocations: 2,
+                bytes: 810,
+                peak_bytes: 800,
+            }),
+            stats
+        );
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!("512B", format_bytes(512));
+        assert_eq!("1.5KiB", format_bytes(1536));
+        assert_eq!("3.0MiB", format_bytes(3 * 1024 * 1024));
+    }
+}
@@ -0,0 +1,225 @@
+//! Robustness of the readers: mutated copies of the real inputs must either
+//! parse or fail with an [`crate::input::InputError`], never panic or hang.
+
+use std::fmt;
+use std::panic;
+use std::panic::RefUnwindSafe;
+use std::sync::mpsc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::thread;
+use std::time::Duration;
+
+use crate::input::InputError;
+use crate::rng::Rng;
+use crate::runner;
+
+/// Characters a corrupted line gets, picked to trip up splitting on
+/// separators and parsing numbers.
+static CORRUPTIONS: &[char] = &[' ', ',', '-', '>', '=', '|', '0', '9', 'x', 'A', '\t', '#', 'é'];
+
+/// Applies one to three random edits to the lines of `raw`: truncating,
+/// dropping, duplicating, swapping, corrupting or blanking them. Returns the
+/// mutated input and a description of the edits.
+pub fn mutate(rng: &mut Rng, raw: &str) -> (String, String) {
+    let mut lines: Vec<String> = raw.lines().map(String::from).collect();
+    let mut edits: Vec<String> = Vec::new();
+    for _ in 0..1 + rng.below(3) {
+        if lines.is_empty() {
+            break;
+        }
+        let idx = rng.below(lines.len());
+        let line = idx + 1;
+        match rng.below(7) {
+            0 => {
+                let text = &mut lines[idx];
+                let cut = text.char_indices().nth(rng.below(text.chars().count() + 1));
+                text.truncate(cut.map_or(text.len(), |(byte, _)| byte));
+                lines.truncate(line);
+                edits.push(format!("truncated at line {}", line));
+            }
+            1 => {
+                lines.remove(idx);
+                edits.push(format!("dropped line {}", line));
+            }
+            2 => {
+                lines.insert(idx, lines[idx].clone());
+                edits.push(format!("duplicated line {}", line));
+            }
+            3 => {
+                let other = rng.below(lines.len());
+                lines.swap(idx, other);
+                edits.push(format!("swapped lines {} and {}", line, other + 1));
+            }
+            4 => {
+                let mut chars: Vec<char> = lines[idx].chars().collect();
+                let at = rng.below(chars.len() + 1);
+                let corruption = *rng.choose(CORRUPTIONS);
+                match chars.get_mut(at) {
+                    Some(c) => *c = corruption,
+                    None => chars.push(corruption),
+                }
+                lines[idx] = chars.into_iter().collect();
+                edits.push(format!("corrupted line {} with {:?}", line, corruption));
+            }
+            5 => {
+                lines.insert(idx, String::new());
+                edits.push(format!("inserted a blank line at {}", line));
+            }
+            _ => {
+                lines[idx].clear();
+                edits.push(format!("blanked line {}", line));
+            }
+        }
+    }
+    let mut mutated = lines.join("\n");
+    if rng.chance(1, 2) {
+        mutated.push('\n');
+    }
+    (mutated, edits.join(", "))
+}
+
+/// A mutated input its reader panicked on or did not finish in time.
+#[derive(Debug)]
+pub struct Failure {
+    pub seed: u64,
+    pub mutation: String,
+    pub message: String,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "seed {} ({}): {}", self.seed, self.mutation, self.message)
+    }
+}
+
+enum Progress {
+    Started(u64, String),
+    Panicked(String),
+}
+
+/// Reads `cases` mutations of `raw`, from seeds `0..cases`, with `read` on a
+/// separate thread. Gives up on the first mutation taking longer than
+/// `timeout`, leaving its thread behind.
+pub fn failures<F>(raw: &str, read: F, cases: u64, timeout: Duration) -> Vec<Failure>
+where
+    F: Fn(&str) -> Result<(), InputError> + Send + RefUnwindSafe + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    let raw = raw.to_string();
+    thread::spawn(move || {
+        for seed in 0..cases {
+            let (mutated, mutation) = mutate(&mut Rng::new(seed), &raw);
+            if sender.send(Progress::Started(seed, mutation)).is_err() {
+                return;
+            }
+            if let Err(payload) = panic::catch_unwind(|| read(&mutated)) {
+                let _ = sender.send(Progress::Panicked(runner::panic_message(payload.as_ref())));
+            }
+        }
+    });
+
+    let mut found: Vec<Failure> = Vec::new();
+    let mut current = (0, String::new());
+    loop {
+        match receiver.recv_timeout(timeout) {
+            Ok(Progress::Started(seed, mutation)) => current = (seed, mutation),
+            Ok(Progress::Panicked(message)) => found.push(Failure {
+                seed: current.0,
+                mutation: current.1.clone(),
+                message: format!("panicked: {}", message),
+            }),
+            Err(RecvTimeoutError::Timeout) => {
+                found.push(Failure {
+                    seed: current.0,
+                    mutation: current.1,
+                    message: format!("did not finish within {:?}", timeout),
+                });
+                return found;
+            }
+            Err(RecvTimeoutError::Disconnected) => return found,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::input;
+    use crate::y2021;
+    use crate::y2021::day16;
+
+    /// Generous for reading the largest inputs in a debug build.
+    static TIMEOUT: Duration = Duration::from_secs(10);
+
+    #[test]
+    fn test_mutate() {
+        let raw = "1,2\n\n3 4\n5 6\n";
+        let (mutated, mutation) = mutate(&mut Rng::new(4), raw);
+        assert_eq!((mutated.clone(), mutation.clone()), mutate(&mut Rng::new(4), raw));
+        assert!(!mutation.is_empty());
+        let changed = (0..20).filter(|seed| mutate(&mut Rng::new(*seed), raw).0.trim_end() != raw.trim_end()).count();
+        assert!(changed > 10);
+        assert_eq!(String::new(), mutate(&mut Rng::new(1), "").1);
+    }
+
+    fn panicking(raw: &str) -> Result<(), InputError> {
+        if raw.contains('#') {
+            panic!("found #");
+        }
+        Err(input::end_of_input(raw, "nothing"))
+    }
+
+    fn looping(_raw: &str) -> Result<(), InputError> {
+        loop {
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn test_failures() {
+        let found = failures("a\nb\nc\n", panicking, 200, TIMEOUT);
+        assert!(!found.is_empty());
+        assert!(found.iter().all(|failure| failure.message == "panicked: found #" && failure.mutation.contains("'#'")));
+
+        let found = failures("a\n", looping, 5, Duration::from_millis(50));
+        assert_eq!(1, found.len());
+        assert_eq!(0, found[0].seed);
+        assert!(found[0].message.starts_with("did not finish"));
+    }
+
+    type Reader = Arc<dyn Fn(&str) -> Result<(), InputError> + Send + Sync + RefUnwindSafe>;
+
+    /// Every registered reader, plus the unregistered day 16, on its real and
+    /// its test input.
+    #[test]
+    fn every_reader_survives_mutations() {
+        let data_dir = input::data_dir(None);
+        let mut readers: Vec<(&str, Reader)> = y2021::days()
+            .into_iter()
+            .map(|entry| {
+                let load = entry.load;
+                let read: Reader = Arc::new(move |raw| load(raw).map(drop));
+                (entry.data_file, read)
+            })
+            .collect();
+        readers.push((day16::DATA_FILEPATH, Arc::new(|raw| day16::parse(raw).map(drop))));
+        let mut report: Vec<String> = Vec::new();
+        for (data_file, read) in readers {
+            for file in [data_file.to_string(), data_file.replace(".txt", "_test.txt")] {
+                let path = input::data_file(&data_dir, &file);
+                if !Path::new(&path).exists() {
+                    continue;
+                }
+                let raw = input::read_to_string(&path).unwrap();
+                let read = Arc::clone(&read);
+                for failure in failures(&raw, move |raw| read(raw), 200, TIMEOUT) {
+                    report.push(format!("{}: {}", file, failure));
+                }
+            }
+        }
+        assert!(report.is_empty(), "{}", report.join("\n"));
+    }
+}
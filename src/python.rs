@@ -0,0 +1,417 @@
+//! Days that only exist as Python scripts, `src/dayN.py`, run with a local
+//! `python3` and checked against their Rust ports, see `aoc2020 python`.
+
+use std::env;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process;
+use std::process::Command;
+use std::process::Stdio;
+use std::slice;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::answer::Answer;
+use crate::compare::EXIT_MISMATCH;
+use crate::registry;
+use crate::registry::TaskKey;
+use crate::registry::TaskRegistry;
+use crate::registry::ALIAS_YEAR;
+use crate::runner;
+use crate::runner::Failure;
+use crate::runner::TaskReport;
+use crate::runner::EXIT_PANIC;
+
+/// Where the scripts live, independent of the working directory.
+pub static SCRIPTS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+pub static PYTHON: &str = "python3";
+
+/// Every script solves two parts, picked by its second argument.
+pub static PARTS: u32 = 2;
+
+static POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// A day's `dayN.py` script.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Script {
+    pub year: u32,
+    pub day: u32,
+    pub path: PathBuf,
+}
+
+impl Script {
+    /// Default input relative to the data directory, e.g. `2021/day20.txt`.
+    pub fn data_file(&self) -> String {
+        format!("{}/day{}.txt", self.year, self.day)
+    }
+
+    pub fn key(&self, part: u32) -> TaskKey {
+        TaskKey {
+            year: self.year,
+            day: self.day,
+            part,
+        }
+    }
+}
+
+/// Finds the `dayN.py` scripts in `dir`, in day order. They all read their
+/// input from `data/2021`, so they belong to [`ALIAS_YEAR`].
+pub fn scripts(dir: &Path) -> io::Result<Vec<Script>> {
+    let mut scripts = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let day = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix("day")?.strip_suffix(".py")?.parse().ok());
+        if let Some(day) = day {
+            scripts.push(Script {
+                year: ALIAS_YEAR,
+                day,
+                path,
+            });
+        }
+    }
+    scripts.sort_by_key(|script| script.day);
+    Ok(scripts)
+}
+
+/// Resolves a part (`2021/20/1`) or a day (`2021/20`, `day20`) to the
+/// scripts and parts it covers, every part of every script without a `spec`.
+pub fn select<'a>(scripts: &'a [Script], spec: Option<&str>) -> Result<Vec<(&'a Script, u32)>, String> {
+    let all_parts = |script: &'a Script| (1..=PARTS).map(move |part| (script, part));
+    let selected: Vec<(&Script, u32)> = match spec {
+        None => scripts.iter().flat_map(all_parts).collect(),
+        Some(spec) => {
+            if let Ok(key) = spec.parse::<TaskKey>() {
+                scripts
+                    .iter()
+                    .filter(|script| script.key(key.part) == key && (1..=PARTS).contains(&key.part))
+                    .map(|script| (script, key.part))
+                    .collect()
+            } else {
+                let (year, day) = registry::parse_day(spec).ok_or_else(|| format!("Invalid task_id: {}", spec))?;
+                scripts
+                    .iter()
+                    .filter(|script| script.year == year && script.day == day)
+                    .flat_map(all_parts)
+                    .collect()
+            }
+        }
+    };
+    if selected.is_empty() {
+        return Err(format!("No Python script for {}", spec.unwrap_or("any day")));
+    }
+    Ok(selected)
+}
+
+/// The value after `result=` on the last line printing one, as the scripts
+/// end with e.g. `task_no=1, filename='day20.txt', result=5498`.
+pub fn parse_result(stdout: &str) -> Option<Answer> {
+    let line = stdout.lines().rev().find(|line| line.contains("result="))?;
+    let (_, value) = line.rsplit_once("result=")?;
+    let value = value.trim().trim_matches('\'');
+    Some(value.parse().map(Answer::Int).unwrap_or_else(|_| Answer::from(value)))
+}
+
+/// Gives every run its own working directory, also between threads of the
+/// same process.
+static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+/// Runs `part` of `script` on `raw`, giving up after `budget`.
+///
+/// The script runs in a scratch directory holding `raw` where it expects its
+/// input, with leftover `breakpoint()` calls turned into no-ops and stdin
+/// closed so that any other prompt fails instead of waiting. A script exiting
+/// with an error or without printing a result is reported as panicked. Errors
+/// are reserved for failing to run `python3`.
+pub fn run_script(script: &Script, raw: &str, part: u32, budget: Option<Duration>) -> Result<TaskReport, String> {
+    let run = RUNS.fetch_add(1, Ordering::Relaxed);
+    let workdir = env::temp_dir().join(format!("aoc2020_python_{}_{}", process::id(), run));
+    let result = run_in(&workdir, script, raw, part, budget);
+    let _ = fs::remove_dir_all(&workdir);
+    let (answer, duration) = result.map_err(|err| format!("Failed to run {} {}: {}", PYTHON, script.path.display(), err))?;
+    Ok(TaskReport {
+        task_id: script.key(part).to_string(),
+        answer,
+        duration,
+        parse_duration: Duration::ZERO,
+        alloc: None,
+        cached: false,
+    })
+}
+
+/// Output goes to files rather than pipes, so that a chatty script cannot
+/// block on a full pipe while nobody reads it.
+fn run_in(
+    workdir: &Path,
+    script: &Script,
+    raw: &str,
+    part: u32,
+    budget: Option<Duration>,
+) -> io::Result<(Result<Answer, Failure>, Duration)> {
+    let data_dir = workdir.join("data").join(script.year.to_string());
+    fs::create_dir_all(&data_dir)?;
+    let input_file = format!("day{}.txt", script.day);
+    fs::write(data_dir.join(&input_file), raw)?;
+    let stdout_path = workdir.join("stdout");
+    let stderr_path = workdir.join("stderr");
+
+    let start = Instant::now();
+    let mut child = Command::new(PYTHON)
+        .arg(&script.path)
+        .arg(&input_file)
+        .arg(part.to_string())
+        .current_dir(workdir)
+        .env("PYTHONBREAKPOINT", "0")
+        .stdin(Stdio::null())
+        .stdout(File::create(&stdout_path)?)
+        .stderr(File::create(&stderr_path)?)
+        .spawn()?;
+    let deadline = budget.map(|budget| start + budget);
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            let _ = child.kill();
+            child.wait()?;
+            return Ok((Err(Failure::TimedOut(budget.unwrap())), start.elapsed()));
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+    let duration = start.elapsed();
+
+    if !status.success() {
+        let stderr = fs::read_to_string(&stderr_path)?;
+        let message = match stderr.lines().rev().find(|line| !line.trim().is_empty()) {
+            Some(line) => format!("{}: {}", status, line.trim()),
+            None => status.to_string(),
+        };
+        return Ok((Err(Failure::Panicked(message)), duration));
+    }
+    let answer = parse_result(&fs::read_to_string(&stdout_path)?)
+        .ok_or_else(|| Failure::Panicked(String::from("no result= line in the output")));
+    Ok((answer, duration))
+}
+
+/// A part solved by its script and, when one is registered, by its Rust port
+/// on the same input.
+#[derive(Debug)]
+pub struct PortRun {
+    pub key: TaskKey,
+    pub python: TaskReport,
+    /// The answer of the Rust task, or why it has none.
+    pub rust: Option<Result<Answer, String>>,
+}
+
+impl PortRun {
+    /// Whether both sides answered, differently.
+    pub fn disagrees(&self) -> bool {
+        matches!((&self.python.answer, &self.rust), (Ok(python), Some(Ok(rust))) if python != rust)
+    }
+
+    /// One line with both answers, for reporting a mismatch.
+    pub fn describe(&self) -> String {
+        let python = match &self.python.answer {
+            Ok(answer) => answer.to_string().replace('\n', "/"),
+            Err(failure) => failure.to_string(),
+        };
+        let rust = match &self.rust {
+            Some(Ok(answer)) => answer.to_string().replace('\n', "/"),
+            Some(Err(message)) => message.to_string(),
+            None => String::from("not ported"),
+        };
+        format!("{}: python {}, rust {}", self.key, python, rust)
+    }
+}
+
+/// Runs `part` of `script` on `raw`, and the matching task of `registry` if
+/// the day has been ported.
+pub fn run_part(
+    script: &Script,
+    part: u32,
+    raw: &str,
+    registry: &TaskRegistry,
+    budget: Option<Duration>,
+) -> Result<PortRun, String> {
+    let key = script.key(part);
+    let python = run_script(script, raw, part, budget)?;
+    let rust = registry.get(&key.to_string()).map(|task| match runner::run_task_on(task, raw, budget) {
+        Ok(report) => report.answer.map_err(|failure| failure.to_string()),
+        Err(err) => Err(err.to_string()),
+    });
+    Ok(PortRun { key, python, rust })
+}
+
+/// Combines [`runner::exit_code`] for the scripts with [`EXIT_MISMATCH`] for
+/// ports answering differently and [`EXIT_PANIC`] for ports failing.
+pub fn exit_code(runs: &[PortRun]) -> i32 {
+    runs.iter().fold(0, |code, run| {
+        let script = runner::exit_code(slice::from_ref(&run.python));
+        let mismatch = if run.disagrees() { EXIT_MISMATCH } else { 0 };
+        let port = if matches!(run.rust, Some(Err(_))) { EXIT_PANIC } else { 0 };
+        code | script | mismatch | port
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input;
+    use crate::registry::DayEntry;
+    use crate::test_support::Sum;
+    use crate::test_support::INPUT;
+
+    /// Set to skip the tests running scripts on machines without `python3`.
+    static SKIP_PYTHON_ENV: &str = "AOC_SKIP_PYTHON";
+
+    /// Whether to run the scripts, panics when `python3` is missing unless
+    /// [`SKIP_PYTHON_ENV`] is set.
+    fn has_python() -> bool {
+        let found = Command::new(PYTHON)
+            .arg("--version")
+            .stdout(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success());
+        if !found && env::var_os(SKIP_PYTHON_ENV).is_none() {
+            panic!("{} is not available, set {}=1 to skip the tests that need it", PYTHON, SKIP_PYTHON_ENV);
+        }
+        if !found {
+            eprintln!("{} is not available, skipping", PYTHON);
+        }
+        found
+    }
+
+    /// A script for day 99 with the given source, in its own directory.
+    fn fake_script(name: &str, source: &str) -> Script {
+        let dir = env::temp_dir().join(format!("aoc2020_python_test_{}_{}", process::id(), name));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("day99.py");
+        fs::write(&path, source).unwrap();
+        Script {
+            year: ALIAS_YEAR,
+            day: 99,
+            path,
+        }
+    }
+
+    #[test]
+    fn test_scripts() {
+        let days: Vec<u32> = scripts(Path::new(SCRIPTS_DIR)).unwrap().iter().map(|script| script.day).collect();
+        assert_eq!(vec![19, 20, 21, 22], days);
+    }
+
+    #[test]
+    fn test_select() {
+        let scripts = scripts(Path::new(SCRIPTS_DIR)).unwrap();
+        let keys = |spec| -> Vec<String> {
+            select(&scripts, spec)
+                .unwrap()
+                .iter()
+                .map(|(script, part)| script.key(*part).to_string())
+                .collect()
+        };
+        assert_eq!(8, keys(None).len());
+        assert_eq!(vec!["2021/20/1", "2021/20/2"], keys(Some("day20")));
+        assert_eq!(vec!["2021/21/2"], keys(Some("2021/21/2")));
+        assert!(select(&scripts, Some("2021/21/3")).is_err());
+        assert!(select(&scripts, Some("2021/5")).is_err());
+        assert!(select(&scripts, Some("x")).is_err());
+    }
+
+    #[test]
+    fn test_parse_result() {
+        assert_eq!(Some(Answer::Int(5498)), parse_result("task_no=1, filename='day20.txt', result=5498\n"));
+        assert_eq!(
+            Some(Answer::Int(444356092776315)),
+            parse_result("results=Result(p1=1, p2=2)\ntask_no='2', filename='day21.txt', result=444356092776315\n")
+        );
+        assert_eq!(Some(Answer::from("abc")), parse_result("result='abc'"));
+        assert_eq!(None, parse_result("player1_score=1000\n"));
+    }
+
+    #[test]
+    fn test_run_script() {
+        if !has_python() {
+            return;
+        }
+        let script = scripts(Path::new(SCRIPTS_DIR)).unwrap().remove(2);
+        let raw = input::read_to_string(&input::data_file(&input::data_dir(None), "2021/day21_test.txt")).unwrap();
+        let report = run_script(&script, &raw, 1, None).unwrap();
+        assert_eq!("2021/21/1", report.task_id);
+        assert_eq!(Ok(Answer::Int(739785)), report.answer);
+    }
+
+    #[test]
+    fn test_run_script_skips_breakpoints() {
+        if !has_python() {
+            return;
+        }
+        let script = fake_script("breakpoint", "breakpoint()\nprint('result=1')\n");
+        let report = run_script(&script, "", 1, Some(Duration::from_secs(10))).unwrap();
+        assert_eq!(Ok(Answer::Int(1)), report.answer);
+        fs::remove_dir_all(script.path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_run_script_failures() {
+        if !has_python() {
+            return;
+        }
+        let failing = fake_script("failing", "raise RuntimeError('boom')\n");
+        let report = run_script(&failing, "", 1, None).unwrap();
+        assert!(matches!(&report.answer, Err(Failure::Panicked(message)) if message.ends_with("RuntimeError: boom")));
+
+        let silent = fake_script("silent", "print('thinking')\n");
+        let report = run_script(&silent, "", 1, None).unwrap();
+        assert_eq!(Err(Failure::Panicked(String::from("no result= line in the output"))), report.answer);
+
+        let budget = Duration::from_millis(100);
+        let sleepy = fake_script("sleepy", "import time\ntime.sleep(10)\n");
+        let report = run_script(&sleepy, "", 1, Some(budget)).unwrap();
+        assert_eq!(Err(Failure::TimedOut(budget)), report.answer);
+
+        for script in [failing, silent, sleepy] {
+            fs::remove_dir_all(script.path.parent().unwrap()).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_run_part_compares_ports() {
+        if !has_python() {
+            return;
+        }
+        let script = fake_script(
+            "sum",
+            "import sys\nwith open('data/2021/' + sys.argv[1]) as f:\n    print(f'result={sum(int(l) for l in f)}')\n",
+        );
+        let registry = TaskRegistry::from_days(
+            Path::new(input::DEFAULT_DATA_DIR),
+            &[DayEntry::of::<Sum>(ALIAS_YEAR, "day99", "2021/day99.txt")],
+        );
+        let runs: Vec<PortRun> = (1..=2)
+            .map(|part| run_part(&script, part, INPUT, &registry, None).unwrap())
+            .collect();
+        assert_eq!(Ok(Answer::Int(9)), runs[0].python.answer);
+        assert_eq!(Some(Ok(Answer::Int(9))), runs[0].rust);
+        assert!(!runs[0].disagrees());
+        assert!(runs[1].disagrees());
+        assert_eq!("2021/99/2: python 9, rust 24", runs[1].describe());
+        assert_eq!(0, exit_code(&runs[..1]));
+        assert_eq!(EXIT_MISMATCH, exit_code(&runs));
+
+        let bad = run_part(&script, 1, "1\nx\n", &registry, None).unwrap();
+        assert!(bad.python.answer.is_err());
+        assert!(matches!(bad.rust, Some(Err(_))));
+        assert_eq!(EXIT_PANIC, exit_code(&[bad]));
+        fs::remove_dir_all(script.path.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,325 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::day;
+use crate::day::Day;
+use crate::day::CompareFn;
+use crate::day::DayFn;
+use crate::day::GenerateFn;
+use crate::day::LoadFn;
+use crate::input;
+
+/// Year of the `day5_task1` style ids, which predate keeping several years.
+pub static ALIAS_YEAR: u32 = 2021;
+
+/// Identifies a part of a puzzle, written as `2021/5/1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TaskKey {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+}
+
+impl fmt::Display for TaskKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}/{}", self.year, self.day, self.part)
+    }
+}
+
+/// Parses `2021/5/1` or the `day5_task1` alias for [`ALIAS_YEAR`].
+impl FromStr for TaskKey {
+    type Err = String;
+
+    fn from_str(id: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid task_id: {}", id);
+        let numbers: Vec<u32> = match id.strip_prefix("day").and_then(|rest| rest.split_once("_task")) {
+            Some((day, part)) => vec![ALIAS_YEAR, day.parse().map_err(|_| invalid())?, part.parse().map_err(|_| invalid())?],
+            None => id
+                .split('/')
+                .map(|n| n.parse().map_err(|_| invalid()))
+                .collect::<Result<_, _>>()?,
+        };
+        match numbers[..] {
+            [year, day, part] => Ok(TaskKey { year, day, part }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// A single part of a day, e.g. `2021/13/2`.
+pub struct Task {
+    /// `key` as a string, used in reports.
+    pub id: String,
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    /// Solves the whole day, shared by all of its parts.
+    pub run: DayFn,
+    /// Parses the day's input for the REPL.
+    pub load: LoadFn,
+    /// Names of the part's alternative solvers, see [`Day::variants`].
+    pub variants: Vec<&'static str>,
+    /// Runs the default solver and every variant, see `aoc2020 compare`.
+    pub compare: CompareFn,
+    /// Builds a random input of the day, see `aoc2020 generate`.
+    pub generate: GenerateFn,
+    /// Default input, already resolved against the data directory.
+    pub data_path: String,
+    /// Copy of the default input compiled into the binary, read instead of
+    /// `data_path` when present.
+    pub embedded_input: Option<&'static str>,
+}
+
+impl Task {
+    pub fn key(&self) -> TaskKey {
+        TaskKey {
+            year: self.year,
+            day: self.day,
+            part: self.part,
+        }
+    }
+}
+
+/// A day as declared in its year's `days!` list.
+pub struct DayEntry {
+    pub year: u32,
+    pub day: u32,
+    /// Relative to the data directory, e.g. `2021/day5.txt`.
+    pub data_file: &'static str,
+    /// Number of parts to register, see `Day::PARTS`.
+    pub parts: u32,
+    pub run: DayFn,
+    pub load: LoadFn,
+    /// Part and name of each variant.
+    pub variants: Vec<(u32, &'static str)>,
+    pub compare: CompareFn,
+    pub generate: GenerateFn,
+}
+
+impl DayEntry {
+    /// Registers `D` as the module `name`, e.g. `day5`.
+    pub fn of<D: Day + 'static>(year: u32, name: &str, data_file: &'static str) -> Self {
+        let day = name
+            .strip_prefix("day")
+            .and_then(|n| n.parse().ok())
+            .unwrap_or_else(|| panic!("Invalid day name: {}", name));
+        DayEntry {
+            year,
+            day,
+            data_file,
+            parts: D::PARTS,
+            run: day::run::<D>,
+            load: day::load::<D>,
+            variants: D::variants().iter().map(|variant| (variant.part, variant.name)).collect(),
+            compare: day::compare::<D>,
+            generate: day::generate::<D>,
+        }
+    }
+}
+
+/// Registered tasks, keyed and ordered by year, day and part.
+pub struct TaskRegistry {
+    tasks: BTreeMap<TaskKey, Task>,
+}
+
+/// Year and day of `2021/3`, or of its alias `day3` for [`ALIAS_YEAR`].
+pub fn parse_day(spec: &str) -> Option<(u32, u32)> {
+    match spec.strip_prefix("day") {
+        Some(day) => Some((ALIAS_YEAR, day.parse().ok()?)),
+        None => {
+            let (year, day) = spec.split_once('/')?;
+            Some((year.parse().ok()?, day.parse().ok()?))
+        }
+    }
+}
+
+impl TaskRegistry {
+    /// Registers the parts each day declares, with data files looked up in
+    /// `data_dir`.
+    pub fn from_days(data_dir: &Path, days: &[DayEntry]) -> Self {
+        let mut tasks = BTreeMap::new();
+        for entry in days {
+            let data_path = input::data_file(data_dir, entry.data_file);
+            let embedded_input = input::embedded(entry.data_file);
+            for part in 1..=entry.parts {
+                let key = TaskKey {
+                    year: entry.year,
+                    day: entry.day,
+                    part,
+                };
+                let variants: Vec<&'static str> = entry
+                    .variants
+                    .iter()
+                    .filter(|(variant_part, _)| *variant_part == part)
+                    .map(|(_, name)| *name)
+                    .collect();
+                for (idx, name) in variants.iter().enumerate() {
+                    assert!(*name != day::DEFAULT_VARIANT, "{} cannot have a variant named {}", key, name);
+                    assert!(!variants[..idx].contains(name), "{} has the variant {} twice", key, name);
+                }
+                let task = Task {
+                    id: key.to_string(),
+                    year: entry.year,
+                    day: entry.day,
+                    part,
+                    run: entry.run,
+                    load: entry.load,
+                    variants,
+                    compare: entry.compare,
+                    generate: entry.generate,
+                    data_path: data_path.clone(),
+                    embedded_input,
+                };
+                assert!(tasks.insert(key, task).is_none(), "{} is registered twice", key);
+            }
+        }
+        assert!(!tasks.is_empty(), "days should have at least one task");
+        TaskRegistry { tasks }
+    }
+
+    /// Looks up `2021/5/1` or its alias `day5_task1`.
+    pub fn get(&self, task_id: &str) -> Option<&Task> {
+        self.tasks.get(&task_id.parse().ok()?)
+    }
+
+    /// Resolves a task (`2021/3/1`), a whole day (`2021/3`), an inclusive range
+    /// of days of one year (`2021/3..2021/7`) or a whole year (`2021`) to the
+    /// matching tasks in registry order.
+    ///
+    /// `day3_task1`, `day3` and `day3..day7` are aliases for [`ALIAS_YEAR`].
+    pub fn select(&self, spec: &str) -> Result<Vec<&Task>, String> {
+        if let Some(task) = self.get(spec) {
+            return Ok(vec![task]);
+        }
+        let invalid = || format!("Invalid task_id: {}", spec);
+        let (year, first, last) = if let Ok(year) = spec.parse::<u32>() {
+            (year, u32::MIN, u32::MAX)
+        } else {
+            let (first, last) = match spec.split_once("..") {
+                Some((first, last)) => (parse_day(first).ok_or_else(invalid)?, parse_day(last).ok_or_else(invalid)?),
+                None => (parse_day(spec).ok_or_else(invalid)?, parse_day(spec).ok_or_else(invalid)?),
+            };
+            if first.0 != last.0 {
+                return Err(format!("{} spans several years", spec));
+            }
+            if first.1 > last.1 {
+                return Err(format!("{} is an empty range, the first day comes after the last", spec));
+            }
+            (first.0, first.1, last.1)
+        };
+        let tasks: Vec<&Task> = self
+            .tasks
+            .values()
+            .filter(|task| task.year == year && first <= task.day && task.day <= last)
+            .collect();
+        if tasks.is_empty() {
+            return Err(format!("No tasks registered for {}", spec));
+        }
+        Ok(tasks)
+    }
+
+    pub fn latest_task_id(&self) -> &str {
+        &self.tasks.values().next_back().unwrap().id
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Task> {
+        self.tasks.values()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Sum;
+
+    fn entry(year: u32, name: &str, parts: u32) -> DayEntry {
+        DayEntry {
+            parts,
+            ..DayEntry::of::<Sum>(year, name, "data.txt")
+        }
+    }
+
+    #[test]
+    fn test_task_key() {
+        let key = TaskKey {
+            year: 2021,
+            day: 5,
+            part: 1,
+        };
+        assert_eq!("2021/5/1", key.to_string());
+        assert_eq!(Ok(key), "2021/5/1".parse());
+        assert_eq!(Ok(key), "day5_task1".parse());
+        assert!("2021/5".parse::<TaskKey>().is_err());
+        assert!("day5".parse::<TaskKey>().is_err());
+    }
+
+    #[test]
+    fn test_keeps_key_order() {
+        let registry = TaskRegistry::from_days(
+            Path::new("/aoc"),
+            &[entry(2021, "day10", 1), entry(2021, "day2", 1), entry(2020, "day25", 1)],
+        );
+        let ids: Vec<&str> = registry.iter().map(|task| task.id.as_str()).collect();
+        assert_eq!(vec!["2020/25/1", "2021/2/1", "2021/10/1"], ids);
+        assert_eq!("2021/10/1", registry.latest_task_id());
+        assert_eq!("/aoc/data.txt", registry.get("day2_task1").unwrap().data_path);
+        assert_eq!(10, registry.get("2021/10/1").unwrap().day);
+        assert!(registry.get("day25_task1").is_none());
+    }
+
+    #[test]
+    fn test_registers_variants_per_part() {
+        let entry = DayEntry {
+            variants: vec![(2, "fast"), (1, "naive"), (2, "closed_form")],
+            ..entry(2021, "day7", 2)
+        };
+        let registry = TaskRegistry::from_days(Path::new("/aoc"), &[entry]);
+        assert_eq!(vec!["naive"], registry.get("2021/7/1").unwrap().variants);
+        assert_eq!(vec!["fast", "closed_form"], registry.get("2021/7/2").unwrap().variants);
+    }
+
+    #[test]
+    #[should_panic(expected = "2021/7/1 cannot have a variant named default")]
+    fn test_rejects_default_variant_name() {
+        let entry = DayEntry {
+            variants: vec![(1, "default")],
+            ..entry(2021, "day7", 1)
+        };
+        TaskRegistry::from_days(Path::new("/aoc"), &[entry]);
+    }
+
+    #[test]
+    fn test_from_days_and_select() {
+        let registry = TaskRegistry::from_days(
+            Path::new("/aoc"),
+            &[
+                entry(2020, "day2", 1),
+                entry(2021, "day2", 2),
+                entry(2021, "day3", 0),
+                entry(2021, "day4", 1),
+            ],
+        );
+        let select = |spec: &str| {
+            registry
+                .select(spec)
+                .map(|tasks| tasks.iter().map(|task| task.id.as_str()).collect::<Vec<_>>())
+        };
+        assert_eq!(Ok(vec!["2021/2/2"]), select("day2_task2"));
+        assert_eq!(Ok(vec!["2021/2/2"]), select("2021/2/2"));
+        assert_eq!(Ok(vec!["2021/2/1", "2021/2/2"]), select("day2"));
+        assert_eq!(Ok(vec!["2020/2/1"]), select("2020/2"));
+        assert_eq!(Ok(vec!["2021/2/1", "2021/2/2", "2021/4/1"]), select("day2..day4"));
+        assert_eq!(Ok(vec!["2021/2/1", "2021/2/2", "2021/4/1"]), select("2021/2..2021/4"));
+        assert_eq!(Ok(vec!["2021/2/1", "2021/2/2", "2021/4/1"]), select("2021"));
+        assert!(select("2020/2..2021/4").is_err());
+        assert_eq!(
+            Err(String::from("day4..day2 is an empty range, the first day comes after the last")),
+            select("day4..day2")
+        );
+        assert!(select("day3").is_err());
+        assert!(select("day2_task3").is_err());
+        assert!(select("foo").is_err());
+    }
+}
@@ -0,0 +1,173 @@
+//! Interactive loop over a day's parsed input, see `aoc2020 repl`.
+
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::day::Session;
+use crate::registry::Task;
+use crate::runner;
+use crate::runner::RunError;
+
+static COMMANDS: &[&str] = &[
+    "part1, part2    solve a part on the loaded input",
+    "reload          read and parse the input again",
+    "stats           size of the input and how long parsing took",
+    "help            list the commands",
+    "quit            leave the REPL",
+];
+
+/// A day's input, parsed once and then queried command by command.
+pub struct Repl<'a> {
+    task: &'a Task,
+    input_path: Option<String>,
+    raw: String,
+    session: Box<dyn Session>,
+    parse_duration: Duration,
+}
+
+type Loaded = (String, Box<dyn Session>, Duration);
+
+impl<'a> Repl<'a> {
+    /// Reads and parses the input of `task`'s day, `input_path` overrides its
+    /// default input.
+    pub fn load(task: &'a Task, input_path: Option<&str>) -> Result<Self, RunError> {
+        let (raw, session, parse_duration) = Self::read(task, input_path)?;
+        Ok(Repl {
+            task,
+            input_path: input_path.map(|path| path.to_string()),
+            raw,
+            session,
+            parse_duration,
+        })
+    }
+
+    fn read(task: &Task, input_path: Option<&str>) -> Result<Loaded, RunError> {
+        let raw = runner::read_task_input(task, input_path)?;
+        let start = Instant::now();
+        let session =
+            (task.load)(&raw).map_err(|err| RunError::input(err, input_path.unwrap_or(&task.data_path)))?;
+        Ok((raw, session, start.elapsed()))
+    }
+
+    pub fn prompt(&self) -> String {
+        format!("{}/{}> ", self.task.year, self.task.day)
+    }
+
+    fn source(&self) -> String {
+        match (&self.input_path, self.task.embedded_input) {
+            (Some(input_path), _) => input_path.to_string(),
+            (None, Some(_)) => format!("embedded copy of {}", self.task.data_path),
+            (None, None) => self.task.data_path.to_string(),
+        }
+    }
+
+    /// Runs a single command line and returns what to print, a day panicking
+    /// is reported as an error.
+    pub fn execute(&mut self, line: &str) -> Result<String, String> {
+        let mut words = line.split_whitespace();
+        let command = match words.next() {
+            Some(command) => command,
+            None => return Ok(String::new()),
+        };
+        let args: Vec<&str> = words.collect();
+        match command {
+            "part1" | "part2" | "reload" | "stats" | "help" if !args.is_empty() => {
+                Err(format!("{} takes no arguments", command))
+            }
+            "part1" | "part2" => {
+                let part = if command == "part1" { 1 } else { 2 };
+                let start = Instant::now();
+                let answer = catch(|| self.session.solve(part))?;
+                let duration = runner::format_duration(start.elapsed());
+                if answer.is_multiline() {
+                    Ok(format!("{}\n({})", answer, duration))
+                } else {
+                    Ok(format!("{} ({})", answer, duration))
+                }
+            }
+            "reload" => {
+                let (raw, session, parse_duration) =
+                    Self::read(self.task, self.input_path.as_deref()).map_err(|err| err.to_string())?;
+                self.raw = raw;
+                self.session = session;
+                self.parse_duration = parse_duration;
+                Ok(format!("Reloaded {}", self.source()))
+            }
+            "stats" => Ok(format!(
+                "input: {}\nsize:  {} bytes, {} lines\nparse: {}",
+                self.source(),
+                self.raw.len(),
+                self.raw.lines().count(),
+                runner::format_duration(self.parse_duration)
+            )),
+            "help" => Ok(COMMANDS.iter().chain(self.session.commands()).copied().collect::<Vec<_>>().join("\n")),
+            _ => catch(|| self.session.inspect(command, &args))?
+                .unwrap_or_else(|| Err(format!("Unknown command: {}, try help", command))),
+        }
+    }
+}
+
+fn catch<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| format!("PANIC {}", runner::panic_message(payload.as_ref())))
+}
+
+/// Reads commands from `input` until `quit` or the end of the input, printing
+/// the prompt and every result to `output`.
+pub fn run(repl: &mut Repl, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    write!(output, "{}", repl.prompt())?;
+    output.flush()?;
+    for line in input.lines() {
+        let line = line?;
+        if matches!(line.trim(), "quit" | "exit") {
+            return Ok(());
+        }
+        match repl.execute(&line) {
+            Ok(text) if text.is_empty() => {}
+            Ok(text) | Err(text) => writeln!(output, "{}", text)?,
+        }
+        write!(output, "{}", repl.prompt())?;
+        output.flush()?;
+    }
+    writeln!(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn task() -> Task {
+        Task {
+            embedded_input: Some("4000000000\n5000000000\n6\n"),
+            ..test_support::task(3, 1)
+        }
+    }
+
+    #[test]
+    fn test_execute() {
+        let task = task();
+        let mut repl = Repl::load(&task, None).unwrap();
+        assert!(repl.execute("part1").unwrap().starts_with("9000000006 ("));
+        assert_eq!(Err(String::from("PANIC Product overflows")), repl.execute("part2"));
+        assert_eq!(Ok(String::from("5000000000")), repl.execute("line 1"));
+        assert_eq!(Err(String::from("No line 7")), repl.execute("line 7"));
+        assert!(repl.execute("frobnicate").unwrap_err().starts_with("Unknown command"));
+        assert!(repl.execute("help").unwrap().contains("line <n>"));
+        assert!(repl.execute("stats").unwrap().contains("24 bytes, 3 lines"));
+        assert_eq!(Ok(String::new()), repl.execute("  "));
+    }
+
+    #[test]
+    fn test_run() {
+        let task = task();
+        let mut repl = Repl::load(&task, None).unwrap();
+        let mut output = Vec::new();
+        run(&mut repl, "line 0\nquit\nline 2\n".as_bytes(), &mut output).unwrap();
+        assert_eq!("2021/3> 4000000000\n2021/3> ", String::from_utf8(output).unwrap());
+    }
+}
@@ -0,0 +1,839 @@
+use std::any::Any;
+use std::fmt;
+use std::io;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::answer::Answer;
+use crate::cache::AnswerCache;
+use crate::day;
+use crate::day::DayFn;
+use crate::day::Progress;
+use crate::info;
+use crate::input;
+use crate::input::InputError;
+use crate::json::Value;
+use crate::memory;
+use crate::memory::AllocStats;
+use crate::registry::Task;
+use crate::registry::TaskKey;
+use crate::registry::TaskRegistry;
+
+/// Why a task has no answer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Failure {
+    /// The task panicked with the given message.
+    Panicked(String),
+    /// The task was still running when its time budget ran out.
+    TimedOut(Duration),
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Panicked(message) => write!(f, "PANIC {}", message),
+            Failure::TimedOut(budget) => write!(f, "TIMEOUT after {}", format_duration(*budget)),
+        }
+    }
+}
+
+/// Exit code bit set when at least one task panicked. Exit code 2 is kept for
+/// errors that stop the whole run, like malformed input.
+pub static EXIT_PANIC: i32 = 1;
+/// Exit code bit set when at least one task timed out.
+pub static EXIT_TIMEOUT: i32 = 4;
+
+#[derive(Debug)]
+pub struct TaskReport {
+    pub task_id: String,
+    pub answer: Result<Answer, Failure>,
+    /// Parse and solve time together.
+    pub duration: Duration,
+    /// Part of `duration` spent parsing the input, zero when the parsed input
+    /// was shared with an earlier part of the same day.
+    pub parse_duration: Duration,
+    /// Only counted with `--alloc-stats`, see [`crate::memory`].
+    pub alloc: Option<AllocStats>,
+    /// Answered from the [`AnswerCache`] without running, all durations are
+    /// zero.
+    pub cached: bool,
+}
+
+impl TaskReport {
+    pub fn solve_duration(&self) -> Duration {
+        self.duration.saturating_sub(self.parse_duration)
+    }
+}
+
+/// Why a task could not produce an answer, other than panicking.
+#[derive(Debug)]
+pub enum RunError {
+    Io { path: String, err: io::Error },
+    Input(InputError),
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunError::Io { path, err } => write!(f, "Failed to read input {}: {}", path, err),
+            RunError::Input(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for RunError {}
+
+impl RunError {
+    /// Attaches `input_path` to an error returned by a day's parser.
+    pub fn input(err: InputError, input_path: &str) -> Self {
+        let file = if input_path == input::STDIN { "<stdin>" } else { input_path };
+        RunError::Input(err.with_file(file))
+    }
+}
+
+pub fn read_input(input_path: &str) -> Result<String, RunError> {
+    input::read_to_string(input_path).map_err(|err| RunError::Io {
+        path: input_path.to_string(),
+        err,
+    })
+}
+
+/// Reads `input_path`, or the default input of `task` when it is `None`.
+pub fn read_task_input(task: &Task, input_path: Option<&str>) -> Result<String, RunError> {
+    match (input_path, task.embedded_input) {
+        (Some(input_path), _) => {
+            info!("{} reads {}", task.id, input_path);
+            read_input(input_path)
+        }
+        (None, Some(raw)) => {
+            info!("{} reads the embedded copy of {}", task.id, task.data_path);
+            Ok(raw.to_string())
+        }
+        (None, None) => {
+            info!("{} reads {}", task.id, task.data_path);
+            read_input(&task.data_path)
+        }
+    }
+}
+
+/// Runs a single task against the input at `input_path`, see [`run_tasks`].
+pub fn run_task(task: &Task, input_path: &str) -> Result<TaskReport, RunError> {
+    let mut reports = run_day(&[task], Some(input_path), None, None)?;
+    Ok(reports.remove(0))
+}
+
+/// Runs a single task on the calling thread, without catching panics.
+pub fn run_task_with(task: &Task, raw: &str) -> Result<TaskReport, InputError> {
+    let run = day::collect(task.run, raw, &[task.part])?;
+    let (answer, solve_duration, alloc) = run.parts.into_iter().next().unwrap();
+    Ok(TaskReport {
+        task_id: task.id.to_string(),
+        answer: Ok(answer),
+        duration: run.parse_duration + solve_duration,
+        parse_duration: run.parse_duration,
+        alloc,
+        cached: false,
+    })
+}
+
+/// Sent from the thread solving a day to the runner waiting for it.
+enum Message {
+    Progress(Progress),
+    Panicked(String),
+    InvalidInput(InputError),
+}
+
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+/// Solver threads alive, including those the runner gave up waiting on.
+static SOLVER_THREADS: AtomicUsize = AtomicUsize::new(0);
+
+/// Number of threads spawned to solve a day that are still running, timed out
+/// ones included.
+pub fn solver_threads() -> usize {
+    SOLVER_THREADS.load(Ordering::SeqCst)
+}
+
+/// Counts a solver thread in [`SOLVER_THREADS`] until dropped.
+struct SolverThread;
+
+impl SolverThread {
+    fn start() -> Self {
+        SOLVER_THREADS.fetch_add(1, Ordering::SeqCst);
+        SolverThread
+    }
+
+    /// Counts a thread only while fewer than `max` are running, atomically so
+    /// that concurrent callers cannot go past `max` together.
+    fn reserve(max: usize) -> Option<Self> {
+        SOLVER_THREADS
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |running| (running < max).then_some(running + 1))
+            .ok()
+            .map(|_| SolverThread)
+    }
+}
+
+impl Drop for SolverThread {
+    fn drop(&mut self) {
+        SOLVER_THREADS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Solves `parts` of a day on a new thread, which is left behind if the runner
+/// gives up waiting on it.
+fn spawn_day(run: DayFn, raw: Arc<str>, parts: Vec<u32>, solver: SolverThread) -> mpsc::Receiver<Message> {
+    let (sender, receiver) = mpsc::channel();
+    thread::Builder::new()
+        .stack_size(WORKER_STACK_SIZE)
+        .spawn(move || {
+            let _solver = solver;
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                run(&raw, &parts, &mut |progress| {
+                    // Nobody is listening anymore once the runner timed out.
+                    let _ = sender.send(Message::Progress(progress));
+                })
+            }));
+            let _ = match result {
+                Ok(Ok(())) => Ok(()),
+                Ok(Err(err)) => sender.send(Message::InvalidInput(err)),
+                Err(payload) => sender.send(Message::Panicked(panic_message(payload.as_ref()))),
+            };
+        })
+        .expect("Failed to spawn task thread");
+    receiver
+}
+
+fn receive(receiver: &mpsc::Receiver<Message>, deadline: Option<Instant>) -> Result<Message, RecvTimeoutError> {
+    match deadline {
+        Some(deadline) => receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    }
+}
+
+/// Runs the parts of a day that `cache` has no answer for, and stores their
+/// answers in it.
+fn run_day(
+    day_tasks: &[&Task],
+    input_path: Option<&str>,
+    budget: Option<Duration>,
+    cache: Option<&AnswerCache>,
+) -> Result<Vec<TaskReport>, RunError> {
+    let raw: Arc<str> = Arc::from(read_task_input(day_tasks[0], input_path)?);
+    let cached: Vec<Option<Answer>> = day_tasks
+        .iter()
+        .map(|task| cache.and_then(|cache| cache.get(&task.id, &raw)))
+        .collect();
+    let uncached: Vec<&Task> = day_tasks
+        .iter()
+        .zip(&cached)
+        .filter(|(_, answer)| answer.is_none())
+        .map(|(task, _)| *task)
+        .collect();
+    let mut solved = if uncached.is_empty() {
+        Vec::new()
+    } else {
+        solve_day(&uncached, Arc::clone(&raw), budget, None)
+            .map_err(|err| RunError::input(err, input_path.unwrap_or(&day_tasks[0].data_path)))?
+    }
+    .into_iter();
+
+    let mut reports = Vec::new();
+    for (task, answer) in day_tasks.iter().zip(cached) {
+        let report = match answer {
+            Some(answer) => {
+                info!("{} is answered from the cache", task.id);
+                TaskReport {
+                    task_id: task.id.to_string(),
+                    answer: Ok(answer),
+                    duration: Duration::ZERO,
+                    parse_duration: Duration::ZERO,
+                    alloc: None,
+                    cached: true,
+                }
+            }
+            None => solved.next().unwrap(),
+        };
+        if let (Some(cache), Ok(answer), false) = (cache, &report.answer, report.cached) {
+            cache.insert(&task.id, &raw, answer.clone());
+        }
+        reports.push(report);
+    }
+    Ok(reports)
+}
+
+/// Runs a single task against `raw` on a worker thread, see [`run_tasks`].
+pub fn run_task_on(task: &Task, raw: &str, budget: Option<Duration>) -> Result<TaskReport, InputError> {
+    let mut reports = solve_day(&[task], Arc::from(raw), budget, None)?;
+    Ok(reports.remove(0))
+}
+
+/// Like [`run_task_on`], unless `max_solvers` solver threads are already
+/// running, see [`solver_threads`], then `None` without running anything.
+pub fn try_run_task_on(
+    task: &Task,
+    raw: &str,
+    budget: Option<Duration>,
+    max_solvers: usize,
+) -> Option<Result<TaskReport, InputError>> {
+    let solver = SolverThread::reserve(max_solvers)?;
+    Some(solve_day(&[task], Arc::from(raw), budget, Some(solver)).map(|mut reports| reports.remove(0)))
+}
+
+/// Runs consecutive parts of the same day on a worker thread, giving each part
+/// at most `budget`.
+///
+/// A part that panics or times out is reported as such, and the remaining parts
+/// are picked up by a fresh thread. The first thread takes the `reserved` slot
+/// when there is one.
+fn solve_day(
+    day_tasks: &[&Task],
+    raw: Arc<str>,
+    budget: Option<Duration>,
+    mut reserved: Option<SolverThread>,
+) -> Result<Vec<TaskReport>, InputError> {
+    let mut reports: Vec<TaskReport> = Vec::new();
+    while reports.len() < day_tasks.len() {
+        let pending = &day_tasks[reports.len()..];
+        let parts = pending.iter().map(|task| task.part).collect();
+        let solver = reserved.take().unwrap_or_else(SolverThread::start);
+        let receiver = spawn_day(pending[0].run, Arc::clone(&raw), parts, solver);
+        let mut parse_duration = Duration::ZERO;
+        for task in pending {
+            let start = Instant::now();
+            let deadline = budget.map(|budget| start + budget);
+            let outcome = loop {
+                match receive(&receiver, deadline) {
+                    Ok(Message::Progress(Progress::Parsed(duration))) => parse_duration = duration,
+                    Ok(Message::Progress(Progress::Solved(answer, duration, alloc))) => break Ok((answer, duration, alloc)),
+                    Ok(Message::Panicked(message)) => break Err(Failure::Panicked(message)),
+                    Ok(Message::InvalidInput(err)) => return Err(err),
+                    Err(RecvTimeoutError::Timeout) => break Err(Failure::TimedOut(budget.unwrap())),
+                    Err(RecvTimeoutError::Disconnected) => {
+                        break Err(Failure::Panicked(String::from("task thread exited without an answer")))
+                    }
+                }
+            };
+            let failed = outcome.is_err();
+            let (answer, duration, alloc) = match outcome {
+                Ok((answer, solve_duration, alloc)) => (Ok(answer), parse_duration + solve_duration, alloc),
+                Err(failure) => (Err(failure), start.elapsed(), None),
+            };
+            reports.push(TaskReport {
+                task_id: task.id.to_string(),
+                answer,
+                duration,
+                parse_duration,
+                alloc,
+                cached: false,
+            });
+            parse_duration = Duration::ZERO;
+            if failed {
+                break;
+            }
+        }
+    }
+    Ok(reports)
+}
+
+/// Runs `tasks`, parsing the input only once for consecutive parts of the same
+/// day.
+///
+/// Days are spread over `jobs` threads, the reports still come back in the
+/// order of `tasks`. Each task reads its default input unless `input_path` is
+/// given. Panics and tasks running longer than `budget` are reported per task
+/// and do not stop the others, unreadable or malformed input stops the run.
+///
+/// With a `cache`, tasks it has an answer for are not run and the answers of
+/// the others are added to it.
+pub fn run_tasks(
+    tasks: &[&Task],
+    input_path: Option<&str>,
+    jobs: usize,
+    budget: Option<Duration>,
+    cache: Option<&AnswerCache>,
+) -> Result<Vec<TaskReport>, RunError> {
+    let days: Vec<&[&Task]> = tasks
+        .chunk_by(|a, b| a.year == b.year && a.day == b.day && a.data_path == b.data_path)
+        .collect();
+    let mut reports = Vec::new();
+    for day_reports in parallel_map(&days, jobs, |day_tasks| run_day(day_tasks, input_path, budget, cache)) {
+        reports.extend(day_reports?);
+    }
+    Ok(reports)
+}
+
+/// Combines [`EXIT_PANIC`] and [`EXIT_TIMEOUT`] for the failed tasks, zero
+/// when every task has an answer.
+pub fn exit_code(reports: &[TaskReport]) -> i32 {
+    reports.iter().fold(0, |code, report| match report.answer {
+        Ok(_) => code,
+        Err(Failure::Panicked(_)) => code | EXIT_PANIC,
+        Err(Failure::TimedOut(_)) => code | EXIT_TIMEOUT,
+    })
+}
+
+/// Stack size of worker threads, matching the main thread so that deeply
+/// recursive solutions behave the same in parallel.
+static WORKER_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Applies `f` to every item on up to `jobs` threads and returns the results
+/// in the order of `items`.
+///
+/// With a single job everything runs on the calling thread.
+pub fn parallel_map<T: Sync, R: Send>(items: &[T], jobs: usize, f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    if jobs <= 1 || items.len() <= 1 {
+        return items.iter().map(f).collect();
+    }
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new(items.iter().map(|_| None).collect());
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            thread::Builder::new()
+                .stack_size(WORKER_STACK_SIZE)
+                .spawn_scoped(scope, || loop {
+                    let idx = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(idx) else { break };
+                    let result = f(item);
+                    results.lock().unwrap()[idx] = Some(result);
+                })
+                .expect("Failed to spawn worker thread");
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("Every item is processed"))
+        .collect()
+}
+
+/// Runs every registered task against its default input, reported in registry
+/// order.
+pub fn run_all(
+    registry: &TaskRegistry,
+    jobs: usize,
+    budget: Option<Duration>,
+    cache: Option<&AnswerCache>,
+) -> Result<Vec<TaskReport>, RunError> {
+    let tasks: Vec<&Task> = registry.iter().collect();
+    run_tasks(&tasks, None, jobs, budget, cache)
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// Allocation count, bytes allocated and peak bytes as table cells.
+fn format_alloc(alloc: &AllocStats) -> [String; 3] {
+    [
+        alloc.allocations.to_string(),
+        memory::format_bytes(alloc.bytes),
+        memory::format_bytes(alloc.peak_bytes),
+    ]
+}
+
+fn answer_text(report: &TaskReport) -> String {
+    match &report.answer {
+        Ok(answer) => answer.to_string(),
+        Err(failure) => failure.to_string(),
+    }
+}
+
+/// Renders reports as a table with one row per task, then the `sum of task
+/// wall time` row adding up their times and the `wall time` row with the
+/// elapsed time of the whole run, which is shorter when tasks ran in parallel.
+///
+/// The sum stands in for the total CPU time, which is not measured: std has no
+/// per-thread CPU clock, and a task's wall time also counts the time its
+/// thread waited for a CPU.
+///
+/// Allocation columns are added when the reports have counted allocations, the
+/// sum row shows the highest peak rather than a sum. Cached answers show
+/// `cached` instead of their times.
+///
+/// Multi-line answers continue on the following lines of the answer column.
+pub fn format_table(reports: &[TaskReport], wall: Duration) -> String {
+    let id_header = "task_id";
+    let answer_header = "answer";
+    let sum_label = "sum of task wall time";
+    let wall_label = "wall time";
+    let id_width = reports
+        .iter()
+        .map(|r| r.task_id.len())
+        .chain([id_header.len(), sum_label.len(), wall_label.len()])
+        .max()
+        .unwrap();
+    let answer_width = reports
+        .iter()
+        .flat_map(|r| answer_text(r).lines().map(|l| l.len()).collect::<Vec<_>>())
+        .chain([answer_header.len()])
+        .max()
+        .unwrap();
+
+    let mut table = String::new();
+    let mut push_row = |id: &str, answer: &str, parse: &str, solve: &str, time: &str, alloc: [&str; 3]| {
+        let row = format!(
+            "{:<id_width$}  {:<answer_width$}  {:<10}  {:<10}  {:<10}  {:<10}  {:<10}  {}",
+            id, answer, parse, solve, time, alloc[0], alloc[1], alloc[2]
+        );
+        table.push_str(row.trim_end());
+        table.push('\n');
+    };
+
+    let show_alloc = reports.iter().any(|report| report.alloc.is_some());
+    let alloc_header = if show_alloc { ["allocs", "allocated", "peak"] } else { [""; 3] };
+    push_row(id_header, answer_header, "parse", "solve", "time", alloc_header);
+    let mut total_parse = Duration::ZERO;
+    let mut total = Duration::ZERO;
+    let mut total_alloc = AllocStats::default();
+    for report in reports {
+        let answer = answer_text(report);
+        let mut answer_lines = answer.lines();
+        let first_line = answer_lines.next().unwrap_or("");
+        let alloc = report.alloc.map(|alloc| format_alloc(&alloc)).unwrap_or_default();
+        let times = if report.cached {
+            [String::new(), String::new(), String::from("cached")]
+        } else {
+            [
+                format_duration(report.parse_duration),
+                format_duration(report.solve_duration()),
+                format_duration(report.duration),
+            ]
+        };
+        push_row(
+            &report.task_id,
+            first_line,
+            &times[0],
+            &times[1],
+            &times[2],
+            alloc.each_ref().map(String::as_str),
+        );
+        for line in answer_lines {
+            push_row("", line, "", "", "", [""; 3]);
+        }
+        total_parse += report.parse_duration;
+        total += report.duration;
+        if let Some(alloc) = report.alloc {
+            total_alloc.allocations += alloc.allocations;
+            total_alloc.bytes += alloc.bytes;
+            total_alloc.peak_bytes = total_alloc.peak_bytes.max(alloc.peak_bytes);
+        }
+    }
+    let total_alloc = if show_alloc { format_alloc(&total_alloc) } else { Default::default() };
+    push_row(
+        sum_label,
+        "",
+        &format_duration(total_parse),
+        &format_duration(total - total_parse),
+        &format_duration(total),
+        total_alloc.each_ref().map(String::as_str),
+    );
+    push_row(wall_label, "", "", "", &format_duration(wall), [""; 3]);
+    table
+}
+
+/// Renders a single run of the part `key` as one line of JSON, without a
+/// trailing newline.
+///
+/// `input` is left out without an `input_path`, `cached` unless the answer
+/// came from the cache.
+pub fn format_json(key: TaskKey, input_path: Option<&str>, report: &TaskReport) -> String {
+    let (answer, status) = match &report.answer {
+        Ok(answer) => (answer.to_json(), "ok"),
+        Err(Failure::Panicked(_)) => (Value::Null, "panic"),
+        Err(Failure::TimedOut(_)) => (Value::Null, "timeout"),
+    };
+    let mut entries = vec![
+        (String::from("id"), Value::from(report.task_id.as_str())),
+        (String::from("year"), Value::from(key.year as i64)),
+        (String::from("day"), Value::from(key.day as i64)),
+        (String::from("part"), Value::from(key.part as i64)),
+        (String::from("answer"), answer),
+        (String::from("duration_ms"), Value::from(report.duration.as_secs_f64() * 1000.0)),
+        (String::from("parse_ms"), Value::from(report.parse_duration.as_secs_f64() * 1000.0)),
+    ];
+    if let Some(alloc) = report.alloc {
+        entries.push((String::from("allocations"), Value::from(alloc.allocations as i64)));
+        entries.push((String::from("allocated_bytes"), Value::from(alloc.bytes as i64)));
+        entries.push((String::from("peak_bytes"), Value::from(alloc.peak_bytes as i64)));
+    }
+    if let Some(input_path) = input_path {
+        entries.push((String::from("input"), Value::from(input_path)));
+    }
+    if report.cached {
+        entries.push((String::from("cached"), Value::from(true)));
+    }
+    entries.push((String::from("status"), Value::from(status)));
+    match &report.answer {
+        Ok(_) => {}
+        Err(Failure::Panicked(message)) => entries.push((String::from("error"), Value::from(message.as_str()))),
+        Err(failure) => entries.push((String::from("error"), Value::from(failure.to_string().as_str()))),
+    }
+    Value::Object(entries).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day;
+    use crate::day::Day;
+    use crate::test_support::task;
+    use crate::test_support::INPUT;
+    use crate::test_support::OVERFLOWING;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!("1.500ms", format_duration(Duration::from_micros(1500)))
+    }
+
+    struct Sleepy;
+
+    impl Day for Sleepy {
+        type Input = ();
+
+        fn parse(_: &str) -> Result<Self::Input, InputError> {
+            Ok(())
+        }
+
+        fn part1(_: &Self::Input) -> Answer {
+            thread::sleep(Duration::from_millis(500));
+            Answer::Int(1)
+        }
+
+        fn part2(_: &Self::Input) -> Answer {
+            Answer::Int(2)
+        }
+    }
+
+    /// Parts of day 1 whose second part panics.
+    fn overflowing(part: u32) -> Task {
+        Task {
+            embedded_input: Some(OVERFLOWING),
+            ..task(1, part)
+        }
+    }
+
+    #[test]
+    fn test_run_tasks_isolates_failures() {
+        let sleepy = |part| Task {
+            run: day::run::<Sleepy>,
+            ..task(2, part)
+        };
+        let tasks = [overflowing(2), overflowing(1), sleepy(1), sleepy(2)];
+        let tasks: Vec<&Task> = tasks.iter().collect();
+        let reports = run_tasks(&tasks, None, 2, Some(Duration::from_millis(50)), None).unwrap();
+        let answers: Vec<_> = reports.iter().map(|report| report.answer.clone()).collect();
+        assert_eq!(
+            vec![
+                Err(Failure::Panicked(String::from("Product overflows"))),
+                Ok(Answer::Int(9000000000)),
+                Err(Failure::TimedOut(Duration::from_millis(50))),
+                Ok(Answer::Int(2)),
+            ],
+            answers
+        );
+        assert!(solver_threads() >= 1, "the timed out part is still running");
+        assert_eq!(EXIT_PANIC | EXIT_TIMEOUT, exit_code(&reports));
+        assert_eq!(0, exit_code(&reports[1..2]));
+    }
+
+    #[test]
+    fn test_try_run_task_on() {
+        assert!(try_run_task_on(&task(1, 1), INPUT, None, 0).is_none());
+        let report = try_run_task_on(&task(1, 1), INPUT, None, usize::MAX).unwrap().unwrap();
+        assert_eq!(Ok(Answer::Int(9)), report.answer);
+    }
+
+    #[test]
+    fn test_run_tasks_shares_parse_within_a_day() {
+        let tasks = [task(1, 1), task(1, 1), task(2, 1)];
+        let tasks: Vec<&Task> = tasks.iter().collect();
+        let reports = run_tasks(&tasks, None, 1, None, None).unwrap();
+        assert_eq!(3, reports.len());
+        assert_eq!(Duration::ZERO, reports[1].parse_duration);
+        assert_eq!(reports[1].duration, reports[1].solve_duration());
+        assert!(matches!(run_tasks(&tasks, Some("/nonexistent/day1.txt"), 2, None, None), Err(RunError::Io { .. })));
+    }
+
+    #[test]
+    fn test_run_tasks_with_cache() {
+        let tasks = [overflowing(1), overflowing(2)];
+        let tasks: Vec<&Task> = tasks.iter().collect();
+        let cache = AnswerCache::default();
+        let reports = run_tasks(&tasks, None, 1, None, Some(&cache)).unwrap();
+        assert!(reports.iter().all(|report| !report.cached));
+        assert_eq!(1, cache.len(), "the panicking part is not cached");
+
+        let reports = run_tasks(&tasks, None, 1, None, Some(&cache)).unwrap();
+        assert!(reports[0].cached);
+        assert_eq!(Ok(Answer::Int(9000000000)), reports[0].answer);
+        assert!(!reports[1].cached);
+        assert!(reports[1].answer.is_err());
+    }
+
+    #[test]
+    fn test_parallel_map_keeps_order() {
+        let items: Vec<u64> = (0..20).collect();
+        let squares = parallel_map(&items, 4, |n| {
+            thread::sleep(Duration::from_micros(20 - n));
+            n * n
+        });
+        assert_eq!(items.iter().map(|n| n * n).collect::<Vec<_>>(), squares);
+    }
+
+    #[test]
+    fn test_run_task_input_error() {
+        let path = std::env::temp_dir().join("aoc2020_runner_input_error.txt");
+        std::fs::write(&path, "1\nbad\n").unwrap();
+        let path = path.to_str().unwrap();
+        match run_task(&task(1, 1), path) {
+            Err(RunError::Input(err)) => {
+                assert_eq!(Some(path), err.file.as_deref());
+                assert_eq!(2, err.line);
+            }
+            other => panic!("Expected an input error, got {:?}", other),
+        }
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_format_json() {
+        let report = TaskReport {
+            task_id: String::from("day13_task2"),
+            answer: Ok(Answer::Grid(vec![String::from("#."), String::from(".#")])),
+            duration: Duration::from_micros(1500),
+            parse_duration: Duration::from_micros(500),
+            alloc: Some(AllocStats {
+                allocations: 3,
+                bytes: 96,
+                peak_bytes: 64,
+            }),
+            cached: false,
+        };
+        assert_eq!(
+            r##"{"id":"day13_task2","year":2021,"day":13,"part":2,"answer":["#.",".#"],"duration_ms":1.5,"parse_ms":0.5,"allocations":3,"allocated_bytes":96,"peak_bytes":64,"input":"-","status":"ok"}"##,
+            format_json(task(13, 2).key(), Some("-"), &report)
+        );
+
+        let panicked = TaskReport {
+            task_id: String::from("day3_task1"),
+            answer: Err(Failure::Panicked(String::from("boom"))),
+            duration: Duration::from_millis(2),
+            parse_duration: Duration::ZERO,
+            alloc: None,
+            cached: false,
+        };
+        assert_eq!(
+            r#"{"id":"day3_task1","year":2021,"day":3,"part":1,"answer":null,"duration_ms":2,"parse_ms":0,"status":"panic","error":"boom"}"#,
+            format_json(task(3, 1).key(), None, &panicked)
+        );
+
+        let cached = TaskReport {
+            task_id: String::from("day3_task1"),
+            answer: Ok(Answer::Int(198)),
+            duration: Duration::ZERO,
+            parse_duration: Duration::ZERO,
+            alloc: None,
+            cached: true,
+        };
+        assert_eq!(
+            r#"{"id":"day3_task1","year":2021,"day":3,"part":1,"answer":198,"duration_ms":0,"parse_ms":0,"cached":true,"status":"ok"}"#,
+            format_json(task(3, 1).key(), None, &cached)
+        );
+    }
+
+    #[test]
+    fn test_format_table() {
+        let reports = vec![
+            TaskReport {
+                task_id: String::from("day1_task1"),
+                answer: Ok(Answer::Int(1226)),
+                duration: Duration::from_millis(2),
+                parse_duration: Duration::from_millis(1),
+                alloc: None,
+                cached: false,
+            },
+            TaskReport {
+                task_id: String::from("day13_task2"),
+                answer: Ok(Answer::Grid(vec![String::from("#.#"), String::from(".#.")])),
+                duration: Duration::from_millis(3),
+                parse_duration: Duration::ZERO,
+                alloc: None,
+                cached: false,
+            },
+            TaskReport {
+                task_id: String::from("day14_task1"),
+                answer: Err(Failure::TimedOut(Duration::from_millis(5))),
+                duration: Duration::from_millis(5),
+                parse_duration: Duration::ZERO,
+                alloc: None,
+                cached: false,
+            },
+            TaskReport {
+                task_id: String::from("day2_task1"),
+                answer: Ok(Answer::Int(150)),
+                duration: Duration::ZERO,
+                parse_duration: Duration::ZERO,
+                alloc: None,
+                cached: true,
+            },
+        ];
+        let expected = "\
+task_id                answer                 parse       solve       time
+day1_task1             1226                   1.000ms     1.000ms     2.000ms
+day13_task2            #.#                    0.000ms     3.000ms     3.000ms
+                       .#.
+day14_task1            TIMEOUT after 5.000ms  0.000ms     5.000ms     5.000ms
+day2_task1             150                                            cached
+sum of task wall time                         1.000ms     9.000ms     10.000ms
+wall time                                                             9.000ms
+";
+        assert_eq!(expected, format_table(&reports, Duration::from_millis(9)))
+    }
+
+    #[test]
+    fn test_format_table_with_alloc() {
+        let report = |task_id: &str, alloc| TaskReport {
+            task_id: task_id.to_string(),
+            answer: Ok(Answer::Int(7)),
+            duration: Duration::from_millis(1),
+            parse_duration: Duration::ZERO,
+            alloc,
+            cached: false,
+        };
+        let alloc = |allocations, bytes, peak_bytes| AllocStats {
+            allocations,
+            bytes,
+            peak_bytes,
+        };
+        let reports = vec![
+            report("day12_task1", Some(alloc(10, 4096, 2048))),
+            report("day12_task2", Some(alloc(5, 512, 3072))),
+        ];
+        let expected = "\
+task_id                answer  parse       solve       time        allocs      allocated   peak
+day12_task1            7       0.000ms     1.000ms     1.000ms     10          4.0KiB      2.0KiB
+day12_task2            7       0.000ms     1.000ms     1.000ms     5           512B        3.0KiB
+sum of task wall time          0.000ms     2.000ms     2.000ms     15          4.5KiB      3.0KiB
+wall time                                              2.000ms
+";
+        assert_eq!(expected, format_table(&reports, Duration::from_millis(2)))
+    }
+}
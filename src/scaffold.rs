@@ -0,0 +1,234 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::registry::ALIAS_YEAR;
+use crate::verify::ANSWERS_FILEPATH;
+
+static TEMPLATE: &str = include_str!("dayTEMPLATE.rs");
+
+/// The crate's directory, where `new` writes whatever the working directory.
+pub static ROOT: &str = env!("CARGO_MANIFEST_DIR");
+
+/// What the template's solvers answer, recorded for the new day until it is
+/// solved.
+static PLACEHOLDER_ANSWER: i64 = 0;
+
+/// The module of `year`, holding its `days!` list.
+fn year_module(year: u32) -> String {
+    format!("src/y{}/mod.rs", year)
+}
+
+pub fn render_template(year: u32, day: u32) -> String {
+    TEMPLATE
+        .replace("YEARTEMPLATE", &year.to_string())
+        .replace("dayTEMPLATE", &format!("day{}", day))
+        .replace("DayTEMPLATE", &format!("Day{}", day))
+}
+
+fn entry_day(line: &str) -> Option<u32> {
+    let (module, _) = line.trim().strip_suffix(',')?.split_once("::")?;
+    module.strip_prefix("day")?.parse().ok()
+}
+
+/// Adds `dayN::DayN` to the `days!` list of a year's module source, read from
+/// `mod_path`, keeping it in day order.
+pub fn register_day(mod_src: &str, mod_path: &str, day: u32) -> Result<String, String> {
+    let lines: Vec<&str> = mod_src.lines().collect();
+    let start = lines
+        .iter()
+        .position(|line| *line == "days!(")
+        .ok_or_else(|| format!("No days! list found in {}", mod_path))?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| *line == ");")
+            .ok_or_else(|| format!("Unterminated days! list in {}", mod_path))?;
+
+    let entries = &lines[start + 1..end];
+    if entries.iter().any(|line| entry_day(line) == Some(day)) {
+        return Err(format!("day{} is already registered in {}", day, mod_path));
+    }
+    let insert_at = entries
+        .iter()
+        .rposition(|line| matches!(entry_day(line), Some(existing) if existing < day))
+        .map_or(start + 1, |idx| start + 1 + idx + 1);
+
+    let new_entry = format!("    day{0}::Day{0},", day);
+    let mut output: Vec<&str> = lines[..insert_at].to_vec();
+    output.push(&new_entry);
+    output.extend(&lines[insert_at..]);
+    Ok(output.join("\n") + "\n")
+}
+
+/// Adds placeholder answers for both parts of a new day, on its test and its
+/// full input, to the source of `data/answers.json` read from `answers_path`.
+pub fn register_answers(answers_src: &str, answers_path: &str, year: u32, day: u32) -> Result<String, String> {
+    let alias = year == ALIAS_YEAR && answers_src.contains(&format!("\"day{}_task", day));
+    if alias || answers_src.contains(&format!("\"{}/{}/", year, day)) {
+        return Err(format!("{}/{} already has answers in {}", year, day, answers_path));
+    }
+    let mut lines: Vec<String> = answers_src.trim_end().lines().map(String::from).collect();
+    if lines.last().map(String::as_str) != Some("]") {
+        return Err(format!("Expected {} to end with a line holding ]", answers_path));
+    }
+    lines.pop();
+    if let Some(last) = lines.last_mut().filter(|line| line.trim_end().ends_with('}')) {
+        last.push(',');
+    }
+    let entries: Vec<String> = [1, 2]
+        .iter()
+        .flat_map(|part| {
+            ["_test", ""].map(|suffix| {
+                format!(
+                    "  {{\"task\": \"{0}/{1}/{2}\", \"input\": \"{0}/day{1}{3}.txt\", \"answer\": {4}}}",
+                    year, day, part, suffix, PLACEHOLDER_ANSWER
+                )
+            })
+        })
+        .collect();
+    lines.push(entries.join(",\n"));
+    lines.push(String::from("]"));
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Creates the module and data files for a new day of `year`, registers it
+/// in the year's module, which has to exist already, and adds placeholder
+/// answers for it, so that the answers file keeps covering every task.
+///
+/// Nothing is written if any of the files already exists.
+pub fn new_day(root: &Path, year: u32, day: u32) -> Result<Vec<PathBuf>, String> {
+    let module = root.join(format!("src/y{}/day{}.rs", year, day));
+    let data = root.join(format!("data/{}/day{}.txt", year, day));
+    let test_data = root.join(format!("data/{}/day{}_test.txt", year, day));
+    let year_mod = root.join(year_module(year));
+    let answers = root.join("data").join(ANSWERS_FILEPATH);
+
+    let existing: Vec<String> = [&module, &data, &test_data]
+        .iter()
+        .filter(|path| path.exists())
+        .map(|path| path.display().to_string())
+        .collect();
+    if !existing.is_empty() {
+        return Err(format!("Refusing to overwrite {}", existing.join(", ")));
+    }
+
+    let mod_src = fs::read_to_string(&year_mod)
+        .map_err(|err| format!("Failed to read {}, is {} set up? {}", year_mod.display(), year, err))?;
+    let mod_src = register_day(&mod_src, &year_module(year), day)?;
+    let answers_src = match fs::read_to_string(&answers) {
+        Ok(answers_src) => answers_src,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => String::from("[\n]\n"),
+        Err(err) => return Err(format!("Failed to read {}: {}", answers.display(), err)),
+    };
+    let answers_src = register_answers(&answers_src, &answers.display().to_string(), year, day)?;
+
+    let write = |path: &Path, content: &str| {
+        fs::write(path, content).map_err(|err| format!("Failed to write {}: {}", path.display(), err))
+    };
+    fs::create_dir_all(root.join(format!("data/{}", year)))
+        .map_err(|err| format!("Failed to create data/{}: {}", year, err))?;
+    write(&module, &render_template(year, day))?;
+    write(&data, "")?;
+    write(&test_data, "")?;
+    write(&year_mod, &mod_src)?;
+    write(&answers, &answers_src)?;
+    Ok(vec![module, data, test_data, year_mod, answers])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify;
+
+    static MOD_SRC: &str = "\
+pub static YEAR: u32 = 2021;
+
+days!(
+    day1::Day1,
+    day3::Day3,
+    // Work in progress.
+    #[allow(dead_code)]
+    day16::Day16,
+);
+";
+
+    #[test]
+    fn test_render_template() {
+        let module = render_template(2021, 17);
+        assert!(module.contains("\"2021/day17.txt\""));
+        assert!(module.contains("\"2021/day17_test.txt\""));
+        assert!(module.contains("impl Day for Day17"));
+        assert!(!module.contains("TEMPLATE"));
+    }
+
+    #[test]
+    fn test_register_day() {
+        let mod_src = register_day(MOD_SRC, "mod.rs", 17).unwrap();
+        assert!(mod_src.contains("    day16::Day16,\n    day17::Day17,\n);"));
+        let mod_src = register_day(&mod_src, "mod.rs", 2).unwrap();
+        assert!(mod_src.contains("    day1::Day1,\n    day2::Day2,\n    day3::Day3,\n"));
+        assert!(register_day(&mod_src, "mod.rs", 3).is_err());
+    }
+
+    #[test]
+    fn test_register_answers() {
+        let answers = "[\n  {\"task\": \"2021/1/1\", \"input\": \"2021/day1.txt\", \"answer\": 7}\n]\n";
+        let answers = register_answers(answers, "answers.json", 2021, 17).unwrap();
+        assert!(answers.starts_with("[\n  {\"task\": \"2021/1/1\", \"input\": \"2021/day1.txt\", \"answer\": 7},\n"));
+        assert!(answers.contains("  {\"task\": \"2021/17/2\", \"input\": \"2021/day17.txt\", \"answer\": 0}\n]\n"));
+        assert_eq!(4, answers.matches("2021/17/").count());
+        assert!(register_answers(&answers, "answers.json", 2021, 17).is_err());
+        assert!(register_answers("[\n  {\"task\": \"day1_task1\"}\n]\n", "answers.json", 2021, 1).is_err());
+        assert!(register_answers("[]", "answers.json", 2021, 17).is_err());
+        assert_eq!(2, register_answers("[\n]\n", "answers.json", 2021, 2).unwrap().matches("2021/day2_test.txt").count());
+    }
+
+    #[test]
+    fn test_new_day_refuses_to_overwrite() {
+        let root = std::env::temp_dir().join("aoc2020_scaffold_test");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/y2021")).unwrap();
+        fs::write(root.join(year_module(2021)), MOD_SRC).unwrap();
+
+        let created = new_day(&root, 2021, 4).unwrap();
+        assert_eq!(5, created.len());
+        assert!(root.join("data/2021/day4_test.txt").exists());
+        assert!(fs::read_to_string(root.join(year_module(2021))).unwrap().contains("    day4::Day4,\n"));
+        assert!(new_day(&root, 2021, 4).is_err());
+        assert!(new_day(&root, 2020, 1).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    /// The answers file of a scaffolded day stays loadable and covers both of
+    /// its parts, with inputs that exist, as `verify` requires.
+    #[test]
+    fn test_new_day_keeps_answers_valid() {
+        let root = std::env::temp_dir().join("aoc2020_scaffold_answers_test");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/y2021")).unwrap();
+        fs::create_dir_all(root.join("data")).unwrap();
+        fs::write(root.join(year_module(2021)), MOD_SRC).unwrap();
+        let answers_path = root.join("data").join(ANSWERS_FILEPATH);
+        let answers_src = fs::read_to_string(Path::new(crate::input::DEFAULT_DATA_DIR).join(ANSWERS_FILEPATH)).unwrap();
+        fs::write(&answers_path, &answers_src).unwrap();
+        let answers_path = answers_path.to_str().unwrap();
+        let before = verify::load_answers(answers_path).unwrap();
+
+        let day = (1..=25)
+            .find(|day| register_day(MOD_SRC, "mod.rs", *day).is_ok() && register_answers(&answers_src, "", 2021, *day).is_ok())
+            .unwrap();
+        new_day(&root, 2021, day).unwrap();
+        let answers = verify::load_answers(answers_path).unwrap();
+        assert_eq!(before[..], answers[..before.len()]);
+        assert_eq!(before.len() + 4, answers.len());
+        for part in [1, 2] {
+            let key = format!("2021/{}/{}", day, part);
+            let new: Vec<_> = answers.iter().filter(|answer| answer.task_id == key).collect();
+            assert_eq!(2, new.len(), "{}", key);
+            assert!(new.iter().all(|answer| Path::new(&answer.input_path).exists()));
+        }
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,372 @@
+//! Minimal HTTP service exposing the solvers, see `aoc2020 serve`.
+//!
+//! `POST /year/{y}/day/{n}/part/{m}` with the puzzle input as the body answers
+//! with the same JSON object as `--format json`, without the `input` field.
+//! `/day/{n}/part/{m}` is short for the year of the `day5_task1` aliases.
+//!
+//! A solver that runs out of its `--timeout` cannot be stopped and keeps its
+//! thread busy, so requests arriving while [`MAX_SOLVERS`] solver threads are
+//! still running are turned away with a 503. At most [`MAX_CONNECTIONS`]
+//! connections are served at once, further ones wait to be accepted.
+
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::sync::Condvar;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use crate::info;
+use crate::json::Value;
+use crate::registry::TaskKey;
+use crate::registry::TaskRegistry;
+use crate::registry::ALIAS_YEAR;
+use crate::runner;
+
+/// Largest accepted request line plus headers.
+pub static MAX_HEAD_SIZE: u64 = 64 * 1024;
+/// Largest accepted body, puzzle inputs are far smaller.
+pub static MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+/// How long a client may take to send its request.
+pub static READ_TIMEOUT: Duration = Duration::from_secs(30);
+/// Solver threads allowed at once, timed out ones included.
+pub static MAX_SOLVERS: usize = 16;
+/// Connections handled at once.
+pub static MAX_CONNECTIONS: usize = 64;
+
+#[derive(Debug, PartialEq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    /// Always JSON.
+    pub body: String,
+}
+
+impl Response {
+    fn error(status: u16, message: &str) -> Self {
+        let body = Value::Object(vec![(String::from("error"), Value::from(message))]);
+        Response {
+            status,
+            body: body.to_string(),
+        }
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+/// Reads a request with a `Content-Length` body, malformed requests come back
+/// as the error response to send.
+pub fn read_request(reader: &mut impl BufRead) -> Result<Request, Response> {
+    let bad_request = |message: &str| Response::error(400, message);
+    let mut head = reader.take(MAX_HEAD_SIZE);
+    let mut read_line = || {
+        let mut line = String::new();
+        match head.read_line(&mut line) {
+            Ok(_) if line.ends_with('\n') => Ok(line.trim_end().to_string()),
+            Ok(_) => Err(bad_request("Incomplete request head")),
+            Err(_) => Err(bad_request("Unreadable request head")),
+        }
+    };
+
+    let request_line = read_line()?;
+    let (method, path) = match request_line.split(' ').collect::<Vec<_>>()[..] {
+        [method, path, version] if version.starts_with("HTTP/1.") => (method.to_string(), path.to_string()),
+        _ => return Err(bad_request(&format!("Invalid request line: {}", request_line))),
+    };
+    let mut content_length: Option<usize> = None;
+    loop {
+        let line = read_line()?;
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| bad_request(&format!("Invalid header: {}", line)))?;
+        if name.eq_ignore_ascii_case("content-length") {
+            let length = value
+                .trim()
+                .parse()
+                .map_err(|_| bad_request(&format!("Invalid Content-Length: {}", value.trim())))?;
+            content_length = Some(length);
+        }
+    }
+
+    let body = match content_length {
+        Some(length) if length > MAX_BODY_SIZE => {
+            return Err(Response::error(413, &format!("Body is larger than {} bytes", MAX_BODY_SIZE)))
+        }
+        Some(length) => {
+            // Grows with the bytes actually received rather than the announced length.
+            let mut body = Vec::new();
+            match reader.take(length as u64).read_to_end(&mut body) {
+                Ok(read) if read == length => body,
+                _ => return Err(bad_request("Body is shorter than its Content-Length")),
+            }
+        }
+        None if method == "POST" => return Err(Response::error(411, "POST requires a Content-Length")),
+        None => Vec::new(),
+    };
+    Ok(Request { method, path, body })
+}
+
+/// Task of `/year/{y}/day/{n}/part/{m}` or `/day/{n}/part/{m}`.
+fn route(path: &str) -> Option<TaskKey> {
+    let (year, path) = match path.strip_prefix("/year/") {
+        Some(rest) => {
+            let (year, path) = rest.split_once('/')?;
+            (year.parse().ok()?, path)
+        }
+        None => (ALIAS_YEAR, path.strip_prefix('/')?),
+    };
+    let (day, part) = path.strip_prefix("day/")?.split_once("/part/")?;
+    Some(TaskKey {
+        year,
+        day: day.parse().ok()?,
+        part: part.parse().ok()?,
+    })
+}
+
+/// Solves the requested part on the request body, giving it at most `budget`,
+/// unless `max_solvers` solver threads are already running.
+pub fn handle(registry: &TaskRegistry, budget: Option<Duration>, max_solvers: usize, request: &Request) -> Response {
+    let key = match route(&request.path) {
+        Some(key) => key,
+        None => {
+            let message = format!("Unknown path {}, expected /year/{{y}}/day/{{n}}/part/{{m}}", request.path);
+            return Response::error(404, &message);
+        }
+    };
+    if request.method != "POST" {
+        return Response::error(405, &format!("{} is not supported, use POST", request.method));
+    }
+    let task = match registry.get(&key.to_string()) {
+        Some(task) => task,
+        None => return Response::error(404, &format!("No solver for {}", key)),
+    };
+    let raw = match std::str::from_utf8(&request.body) {
+        Ok(raw) => raw,
+        Err(_) => return Response::error(400, "Body is not valid UTF-8"),
+    };
+    let outcome = match runner::try_run_task_on(task, raw, budget, max_solvers) {
+        Some(outcome) => outcome,
+        None => return Response::error(503, &format!("{} solvers are still running, retry later", max_solvers)),
+    };
+    match outcome {
+        Ok(report) => Response {
+            status: if report.answer.is_ok() { 200 } else { 500 },
+            body: runner::format_json(task.key(), None, &report),
+        },
+        Err(err) => {
+            let (line, column) = (err.line as i64, err.column as i64);
+            let body = Value::Object(vec![
+                (String::from("error"), Value::from(err.with_file("body").to_string())),
+                (String::from("line"), Value::from(line)),
+                (String::from("column"), Value::from(column)),
+            ]);
+            Response {
+                status: 422,
+                body: body.to_string(),
+            }
+        }
+    }
+}
+
+fn write_response(writer: &mut impl Write, response: &Response) -> io::Result<()> {
+    write!(writer, "HTTP/1.1 {} {}\r\n", response.status, reason(response.status))?;
+    match response.status {
+        405 => write!(writer, "Allow: POST\r\n")?,
+        503 => write!(writer, "Retry-After: 1\r\n")?,
+        _ => {}
+    }
+    write!(
+        writer,
+        "Content-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.body.len(),
+        response.body
+    )?;
+    writer.flush()
+}
+
+fn handle_connection(
+    stream: TcpStream,
+    registry: &TaskRegistry,
+    budget: Option<Duration>,
+    max_solvers: usize,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let response = match read_request(&mut BufReader::new(&stream)) {
+        Ok(request) => {
+            let response = handle(registry, budget, max_solvers, &request);
+            info!("{} {} -> {}", request.method, request.path, response.status);
+            response
+        }
+        Err(response) => response,
+    };
+    write_response(&mut &stream, &response)
+}
+
+/// Counts the connections being handled, [`Slots::acquire`] waits while `max`
+/// of them are.
+struct Slots {
+    used: Mutex<usize>,
+    freed: Condvar,
+    max: usize,
+}
+
+/// A connection counted in [`Slots`] until dropped.
+struct Slot<'a>(&'a Slots);
+
+impl Slots {
+    fn new(max: usize) -> Self {
+        Slots {
+            used: Mutex::new(0),
+            freed: Condvar::new(),
+            max,
+        }
+    }
+
+    fn acquire(&self) -> Slot<'_> {
+        let mut used = self.freed.wait_while(self.used.lock().unwrap(), |used| *used >= self.max).unwrap();
+        *used += 1;
+        Slot(self)
+    }
+}
+
+impl Drop for Slot<'_> {
+    fn drop(&mut self) {
+        *self.0.used.lock().unwrap() -= 1;
+        self.0.freed.notify_one();
+    }
+}
+
+/// Answers requests on `listener` forever, each connection on its own thread
+/// and at most [`MAX_CONNECTIONS`] at once, see [`handle`].
+pub fn serve(listener: &TcpListener, registry: &TaskRegistry, budget: Option<Duration>, max_solvers: usize) {
+    let slots = Slots::new(MAX_CONNECTIONS);
+    thread::scope(|scope| loop {
+        // Waiting here leaves further connections in the listen backlog.
+        let slot = slots.acquire();
+        match listener.accept() {
+            Ok((stream, _)) => {
+                scope.spawn(move || {
+                    let _slot = slot;
+                    if let Err(err) = handle_connection(stream, registry, budget, max_solvers) {
+                        info!("Connection failed: {}", err);
+                    }
+                });
+            }
+            Err(err) => info!("Failed to accept a connection: {}", err),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::registry::DayEntry;
+    use crate::test_support::Sum;
+    use crate::test_support::OVERFLOWING;
+
+    fn registry() -> TaskRegistry {
+        TaskRegistry::from_days(Path::new("/aoc"), &[DayEntry::of::<Sum>(2021, "day3", "2021/day3.txt")])
+    }
+
+    fn post(path: &str, body: &str) -> Request {
+        Request {
+            method: String::from("POST"),
+            path: path.to_string(),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_read_request() {
+        let raw = "POST /day/3/part/1 HTTP/1.1\r\nHost: localhost\r\ncontent-length: 4\r\n\r\n1\n2\nextra";
+        assert_eq!(Ok(post("/day/3/part/1", "1\n2\n")), read_request(&mut raw.as_bytes()));
+        let status = |raw: &str| read_request(&mut raw.as_bytes()).map_err(|response| response.status);
+        assert_eq!(Err(411), status("POST /day/3/part/1 HTTP/1.1\r\n\r\n"));
+        assert_eq!(Err(413), status("POST / HTTP/1.1\r\nContent-Length: 999999999\r\n\r\n"));
+        assert_eq!(Err(400), status("POST / HTTP/1.1\r\nContent-Length: 9\r\n\r\n1\n"));
+        assert_eq!(Err(400), status("POST / HTTP/1.1\r\nContent-Length: 1000000\r\n\r\n1\n"));
+        assert_eq!(Err(400), status("hello\r\n\r\n"));
+    }
+
+    #[test]
+    fn test_handle() {
+        let registry = registry();
+        let response = handle(&registry, None, MAX_SOLVERS, &post("/day/3/part/1", "1\n2\n"));
+        assert_eq!(200, response.status);
+        assert!(response.body.contains(r#""answer":3"#));
+        let response = handle(&registry, None, MAX_SOLVERS, &post("/year/2021/day/3/part/1", "1\n2\n"));
+        assert!(response.body.contains(r#""answer":3"#));
+        assert_eq!(404, handle(&registry, None, MAX_SOLVERS, &post("/year/2020/day/3/part/1", "1\n")).status);
+        assert_eq!(500, handle(&registry, None, MAX_SOLVERS, &post("/day/3/part/2", OVERFLOWING)).status);
+        assert_eq!(404, handle(&registry, None, MAX_SOLVERS, &post("/day/4/part/1", "1\n")).status);
+        assert_eq!(404, handle(&registry, None, MAX_SOLVERS, &post("/days", "1\n")).status);
+        let get = Request {
+            method: String::from("GET"),
+            ..post("/day/3/part/1", "")
+        };
+        assert_eq!(405, handle(&registry, None, MAX_SOLVERS, &get).status);
+        let response = handle(&registry, None, MAX_SOLVERS, &post("/day/3/part/1", "1\nx\n"));
+        assert_eq!(422, response.status);
+        assert!(response.body.contains(r#""line":2,"column":1"#));
+        assert_eq!(503, handle(&registry, None, 0, &post("/day/3/part/1", "1\n")).status);
+    }
+
+    #[test]
+    fn test_slots_wait_for_a_free_one() {
+        let slots = Slots::new(1);
+        let slot = slots.acquire();
+        let acquired = Mutex::new(false);
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                let _slot = slots.acquire();
+                *acquired.lock().unwrap() = true;
+            });
+            thread::sleep(Duration::from_millis(50));
+            assert!(!*acquired.lock().unwrap());
+            drop(slot);
+        });
+        assert!(*acquired.lock().unwrap());
+    }
+
+    #[test]
+    fn test_serve_on_localhost() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || serve(&listener, &registry(), None, MAX_SOLVERS));
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(stream, "POST /day/3/part/1 HTTP/1.1\r\nContent-Length: 6\r\n\r\n4\n5\n6\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+        assert!(response.ends_with(r#""status":"ok"}"#), "{}", response);
+    }
+}
@@ -0,0 +1,89 @@
+//! Fixtures shared by the tests of the runner's modules.
+
+use std::path::Path;
+
+use crate::answer::Answer;
+use crate::day;
+use crate::day::Day;
+use crate::day::Variant;
+use crate::input;
+use crate::input::InputError;
+use crate::registry::Task;
+use crate::rng::Rng;
+
+/// Input of [`task`], part 1 is 9 and part 2 is 24.
+pub static INPUT: &str = "2\n3\n4\n";
+
+/// Input whose product overflows, so part 2 of [`Sum`] panics on it.
+pub static OVERFLOWING: &str = "4000000000\n5000000000\n";
+
+/// One number per line, part 1 sums them and part 2 multiplies them.
+pub struct Sum;
+
+impl Day for Sum {
+    type Input = Vec<i64>;
+
+    const COMMANDS: &'static [&'static str] = &["line <n>        show the n-th number"];
+
+    fn parse(raw: &str) -> Result<Self::Input, InputError> {
+        input::lines(raw).map(|line| line.parse(line.text, "a number")).collect()
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        Answer::Int(input.iter().sum())
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        let product = input.iter().try_fold(1i64, |product, n| product.checked_mul(*n));
+        Answer::Int(product.expect("Product overflows"))
+    }
+
+    fn inspect(input: &Self::Input, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        match (command, args) {
+            ("line", [n]) => Some(
+                n.parse::<usize>()
+                    .ok()
+                    .and_then(|n| input.get(n))
+                    .map(i64::to_string)
+                    .ok_or_else(|| format!("No line {}", n)),
+            ),
+            _ => None,
+        }
+    }
+
+    fn variants() -> Vec<Variant<Self::Input>> {
+        vec![
+            Variant {
+                part: 1,
+                name: "reversed",
+                solve: |input| Answer::Int(input.iter().rev().sum()),
+            },
+            Variant {
+                part: 1,
+                name: "broken",
+                solve: |_| panic!("broken"),
+            },
+        ]
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some((0..size).map(|_| format!("{}\n", rng.range(1..10))).collect())
+    }
+}
+
+/// A part of [`Sum`] registered as 2021 `day`, reading [`INPUT`].
+pub fn task(day: u32, part: u32) -> Task {
+    Task {
+        id: format!("2021/{}/{}", day, part),
+        year: 2021,
+        day,
+        part,
+        run: day::run::<Sum>,
+        load: day::load::<Sum>,
+        variants: if part == 1 { vec!["reversed", "broken"] } else { Vec::new() },
+        compare: day::compare::<Sum>,
+        generate: day::generate::<Sum>,
+        data_path: input::data_file(Path::new(input::DEFAULT_DATA_DIR), &format!("2021/day{}.txt", day)),
+        embedded_input: Some(INPUT),
+    }
+}
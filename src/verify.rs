@@ -0,0 +1,177 @@
+use std::fs;
+use std::path::Path;
+
+use crate::answer::Answer;
+use crate::input;
+use crate::json;
+use crate::registry::TaskRegistry;
+use crate::runner;
+
+/// Expected answers for full and test inputs of every task, in the data
+/// directory.
+pub static ANSWERS_FILEPATH: &str = "answers.json";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpectedAnswer {
+    pub task_id: String,
+    pub input_path: String,
+    pub answer: Answer,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Ok,
+    Mismatch(Answer),
+    Error(String),
+}
+
+/// Reads a JSON array of `{"task": ..., "input": ..., "answer": ...}` entries,
+/// with inputs relative to the directory of the answers file.
+pub fn load_answers(path: &str) -> Result<Vec<ExpectedAnswer>, String> {
+    let answers_dir = Path::new(path).parent().unwrap_or(Path::new(""));
+    let raw = fs::read_to_string(path).map_err(|err| format!("Failed to read {}: {}", path, err))?;
+    let value = json::parse(&raw).map_err(|err| format!("Invalid JSON in {}: {}", path, err))?;
+    let entries = value
+        .as_array()
+        .ok_or_else(|| format!("Expected {} to contain a JSON array", path))?;
+    entries
+        .iter()
+        .enumerate()
+        .map(|(idx, entry)| {
+            let field = |key: &str| {
+                entry
+                    .get(key)
+                    .ok_or_else(|| format!("Entry {} in {} is missing \"{}\"", idx, path, key))
+            };
+            let as_str = |key: &str| {
+                field(key)?
+                    .as_str()
+                    .map(|s| s.to_string())
+                    .ok_or_else(|| format!("Entry {} in {} has non-string \"{}\"", idx, path, key))
+            };
+            Ok(ExpectedAnswer {
+                task_id: as_str("task")?,
+                input_path: input::data_file(answers_dir, &as_str("input")?),
+                answer: Answer::from_json(field("answer")?)
+                    .ok_or_else(|| format!("Entry {} in {} has invalid \"answer\"", idx, path))?,
+            })
+        })
+        .collect()
+}
+
+pub fn verify_one(registry: &TaskRegistry, expected: &ExpectedAnswer) -> Outcome {
+    let task = match registry.get(&expected.task_id) {
+        Some(task) => task,
+        None => return Outcome::Error(format!("Unknown task_id: {}", expected.task_id)),
+    };
+    match runner::run_task(task, &expected.input_path).map(|report| report.answer) {
+        Ok(Ok(answer)) if answer == expected.answer => Outcome::Ok,
+        Ok(Ok(answer)) => Outcome::Mismatch(answer),
+        Ok(Err(failure)) => Outcome::Error(failure.to_string()),
+        Err(err) => Outcome::Error(err.to_string()),
+    }
+}
+
+/// Renders a single verification line, with a diff for mismatches.
+pub fn format_outcome(expected: &ExpectedAnswer, outcome: &Outcome) -> String {
+    let label = format!("{} {}", expected.task_id, expected.input_path);
+    match outcome {
+        Outcome::Ok => format!("ok    {}\n", label),
+        Outcome::Mismatch(actual) => {
+            let mut output = format!("FAIL  {}\n", label);
+            for line in expected.answer.to_string().lines() {
+                output.push_str(&format!("      - {}\n", line));
+            }
+            for line in actual.to_string().lines() {
+                output.push_str(&format!("      + {}\n", line));
+            }
+            output
+        }
+        Outcome::Error(err) => format!("ERROR {}: {}\n", label, err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::DayEntry;
+    use crate::test_support::Sum;
+    use crate::test_support::INPUT;
+
+    fn expected(task_id: &str, input_path: &str, answer: i64) -> ExpectedAnswer {
+        ExpectedAnswer {
+            task_id: task_id.to_string(),
+            input_path: input_path.to_string(),
+            answer: Answer::Int(answer),
+        }
+    }
+
+    #[test]
+    fn test_answers_file_is_valid() {
+        let data_dir = Path::new(input::DEFAULT_DATA_DIR);
+        let registry = crate::tasks_registry(data_dir);
+        let answers = load_answers(&input::data_file(data_dir, ANSWERS_FILEPATH)).unwrap();
+        for task in registry.iter() {
+            assert!(
+                answers
+                    .iter()
+                    .any(|a| registry.get(&a.task_id).is_some_and(|t| t.key() == task.key())),
+                "Missing answers for {}",
+                task.id
+            );
+        }
+        for answer in answers {
+            assert!(registry.get(&answer.task_id).is_some(), "Unknown {}", answer.task_id);
+            assert!(fs::metadata(&answer.input_path).is_ok(), "Missing {}", answer.input_path);
+        }
+    }
+
+    /// A wrong entry fails `cargo test` like it fails `aoc2020 verify`. The
+    /// answer tests of the day modules check some of the same inputs on
+    /// purpose: they keep a day's answers next to its code and run on their
+    /// own with `cargo test dayN`.
+    #[test]
+    fn test_every_answer_verifies() {
+        let data_dir = Path::new(input::DEFAULT_DATA_DIR);
+        let registry = crate::tasks_registry(data_dir);
+        let answers = load_answers(&input::data_file(data_dir, ANSWERS_FILEPATH)).unwrap();
+        let failures: Vec<String> = answers
+            .iter()
+            .map(|expected| (expected, verify_one(&registry, expected)))
+            .filter(|(_, outcome)| *outcome != Outcome::Ok)
+            .map(|(expected, outcome)| format_outcome(expected, &outcome))
+            .collect();
+        assert!(failures.is_empty(), "{}", failures.concat());
+    }
+
+    #[test]
+    fn test_verify_one() {
+        let registry = TaskRegistry::from_days(Path::new("/aoc"), &[DayEntry::of::<Sum>(2021, "day1", "2021/day1.txt")]);
+        let path = std::env::temp_dir().join("aoc2020_verify_one.txt");
+        fs::write(&path, INPUT).unwrap();
+        let path = path.to_str().unwrap();
+        assert_eq!(Outcome::Ok, verify_one(&registry, &expected("day1_task1", path, 9)));
+        assert_eq!(
+            Outcome::Mismatch(Answer::Int(9)),
+            verify_one(&registry, &expected("day1_task1", path, 7))
+        );
+        assert!(matches!(
+            verify_one(&registry, &expected("day2_task1", path, 9)),
+            Outcome::Error(_)
+        ));
+        assert!(matches!(
+            verify_one(&registry, &expected("day1_task1", "/nonexistent/day1.txt", 9)),
+            Outcome::Error(_)
+        ));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_format_mismatch() {
+        let outcome = Outcome::Mismatch(Answer::Int(41));
+        assert_eq!(
+            "FAIL  day1_task1 data/day1.txt\n      - 42\n      + 41\n",
+            format_outcome(&expected("day1_task1", "data/day1.txt", 42), &outcome)
+        )
+    }
+}
@@ -0,0 +1,101 @@
+
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::input;
+use crate::input::InputError;
+use crate::rng::Rng;
+
+pub static DATA_FILEPATH: &str = "2021/day1.txt";
+
+pub type Input = Vec<i64>;
+
+fn count_increasing(nums: &[i64]) -> i64 {
+    nums.windows(2)
+        .map(|window| if window[0] < window[1] { 1 } else { 0 })
+        .sum()
+}
+
+fn sum_windows_of_three(nums: &[i64]) -> Vec<i64> {
+    nums.windows(3)
+        .map(|window| window[0] + window[1] + window[2])
+        .collect()
+}
+
+fn count_increasing_of_three(nums: &[i64]) -> i64 {
+    sum_windows_of_three(nums)
+        .windows(2)
+        .map(|window| if window[0] < window[1] { 1 } else { 0 })
+        .sum()
+}
+
+pub fn parse(raw: &str) -> Result<Input, InputError> {
+    input::lines(raw)
+        .map(|line| line.parse(line.text, "an integer"))
+        .collect()
+}
+
+pub fn part1(nums: &Input) -> i64 {
+    count_increasing(nums)
+}
+
+pub fn part2(nums: &Input) -> i64 {
+    count_increasing_of_three(nums)
+}
+
+pub struct Day1;
+
+impl Day for Day1 {
+    type Input = Input;
+
+    fn parse(raw: &str) -> Result<Input, InputError> {
+        parse(raw)
+    }
+
+    fn part1(input: &Input) -> Answer {
+        Answer::from(part1(input))
+    }
+
+    fn part2(input: &Input) -> Answer {
+        Answer::from(part2(input))
+    }
+
+    /// `size` depths of a random walk.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut depth = rng.range(100..200);
+        let mut raw = String::new();
+        for _ in 0..size {
+            raw.push_str(&format!("{}\n", depth));
+            depth += rng.range(-5..10);
+        }
+        Some(raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day::solve_file;
+    use crate::differential;
+
+    #[test]
+    fn task1() {
+        assert_eq!(Answer::Int(1226), solve_file::<Day1>(DATA_FILEPATH, 1))
+    }
+
+    #[test]
+    fn task2() {
+        assert_eq!(Answer::Int(1252), solve_file::<Day1>(DATA_FILEPATH, 2))
+    }
+
+    /// Windows of three share two depths, so comparing them compares the
+    /// depths three apart.
+    fn reference(nums: &Input, part: u32) -> Answer {
+        let gap = if part == 1 { 1 } else { 3 };
+        Answer::from((gap..nums.len()).filter(|&idx| nums[idx] > nums[idx - gap]).count() as i64)
+    }
+
+    #[test]
+    fn matches_reference() {
+        differential::check::<Day1>(2000, 20, reference)
+    }
+}
@@ -0,0 +1,271 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::debug;
+use crate::input;
+use crate::input::InputError;
+use crate::rng::Rng;
+
+pub static DATA_FILEPATH: &str = "2021/day10.txt";
+
+pub type Input = Vec<String>;
+
+pub fn parse(raw: &str) -> Result<Input, InputError> {
+    input::lines(raw)
+        .map(|line| {
+            line.check_chars(|c| "([{<>}])".contains(c), "a bracket")?;
+            Ok(line.text.to_string())
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+enum LineStatus {
+    Incomplete,
+    IllegalChar(String),
+    Valid,
+}
+
+type LineUnclosedParens = Vec<String>;
+
+fn get_line_status(
+    line: &str,
+    paren_open_to_close: &HashMap<String, String>,
+) -> (LineStatus, LineUnclosedParens) {
+    let mut unclosed_parens = LineUnclosedParens::new();
+    let open_parens: HashSet<String> = paren_open_to_close.keys().map(|k| k.to_string()).collect();
+
+    for paren in line.chars() {
+        if open_parens.contains(&paren.to_string()) {
+            unclosed_parens.push(paren.to_string())
+        } else {
+            // close_paren
+            let last_seen = unclosed_parens.last();
+
+            if last_seen.is_none() {
+                return (LineStatus::IllegalChar(paren.to_string()), unclosed_parens)
+            }
+
+            let last_seen = last_seen.unwrap();
+
+            let expected_close = paren_open_to_close.get(last_seen);
+            match expected_close {
+                Some(expected_close) => {
+                    if paren.to_string() != *expected_close {
+                        return (LineStatus::IllegalChar(paren.to_string()), unclosed_parens)
+                    } else {
+                        unclosed_parens.pop();
+                        continue;
+                    }
+                }
+                _ => {
+                    return (LineStatus::IllegalChar(paren.to_string()), unclosed_parens)
+                }
+            };
+        }
+    }
+
+    if !unclosed_parens.is_empty() {
+        (LineStatus::Incomplete, unclosed_parens)
+    } else {
+        (LineStatus::Valid, unclosed_parens)
+    }
+}
+
+
+pub fn part1(lines: &Input) -> i64 {
+    debug!("lines={:?}", lines);
+    let paren_close_to_open: HashMap<String, String> = HashMap::from([
+        ("{".to_string(), "}".to_string()),
+        ("[".to_string(), "]".to_string()),
+        ("<".to_string(), ">".to_string()),
+        ("(".to_string(), ")".to_string()),
+    ]);
+    let invalid_scores: HashMap<String, i64> = HashMap::from([
+        (")".to_string(), 3),
+        ("]".to_string(), 57),
+        ("}".to_string(), 1197),
+        (">".to_string(), 25137),
+    ]);
+
+    let score: i64 = lines
+        .iter()
+        .map(|l| {
+            let (status, _) = get_line_status(l, &paren_close_to_open);
+            status
+        })
+        .map(|line_result| match line_result {
+            LineStatus::Valid => 0,
+            LineStatus::Incomplete => 0,
+            LineStatus::IllegalChar(c) => {
+                *(invalid_scores.get(&c).expect("Unexpected invalid char"))
+            }
+        })
+        .sum();
+    score
+}
+
+pub fn part2(lines: &Input) -> i64 {
+    debug!("lines={:?}", lines);
+    let paren_close_to_open: HashMap<String, String> = HashMap::from([
+        ("{".to_string(), "}".to_string()),
+        ("[".to_string(), "]".to_string()),
+        ("<".to_string(), ">".to_string()),
+        ("(".to_string(), ")".to_string()),
+    ]);
+    let autocomplete_scores: HashMap<String, i64> = HashMap::from([
+        ("(".to_string(), 1),
+        ("[".to_string(), 2),
+        ("{".to_string(), 3),
+        ("<".to_string(), 4),
+    ]);
+
+    let unclosed_chars: Vec<Vec<String>> = lines
+        .iter()
+        .map(|l| {
+            let (result, unclosed) = get_line_status(l, &paren_close_to_open);
+            (result, unclosed)
+        })
+        .filter(|(result, _)| matches!(result, LineStatus::Incomplete))
+        .map(|(_, unclosed)| unclosed)
+        .collect();
+    
+    let mut results: Vec<i64> = Vec::new();
+    for unclosed in unclosed_chars {
+        let mut total = 0;
+        let mut unclosed = unclosed;
+        unclosed.reverse();
+        for char_score in unclosed.iter().map(|c| autocomplete_scores.get(c).unwrap()) {
+            total = total * 5 + char_score;
+        }
+        results.push(
+            total
+        );
+        
+    };
+    results.sort();
+    let middle_result = results[results.len() / 2];  // Results are expected to always be of odd length.
+    middle_result
+}
+
+static BRACKETS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+
+/// Random chunks of `steps` brackets, leaving at least one open, and the
+/// brackets still open at the end.
+fn open_chunks(rng: &mut Rng, steps: usize) -> (String, Vec<char>) {
+    let mut line = String::new();
+    let mut open: Vec<char> = Vec::new();
+    for _ in 0..steps {
+        match open.pop() {
+            Some(opening) if rng.chance(2, 5) => line.push(BRACKETS.iter().find(|(o, _)| *o == opening).unwrap().1),
+            last => {
+                open.extend(last);
+                let (opening, _) = *rng.choose(&BRACKETS);
+                line.push(opening);
+                open.push(opening);
+            }
+        }
+    }
+    if open.is_empty() {
+        let (opening, _) = *rng.choose(&BRACKETS);
+        line.push(opening);
+        open.push(opening);
+    }
+    (line, open)
+}
+
+pub struct Day10;
+
+impl Day for Day10 {
+    type Input = Input;
+
+    fn parse(raw: &str) -> Result<Input, InputError> {
+        parse(raw)
+    }
+
+    fn part1(input: &Input) -> Answer {
+        Answer::from(part1(input))
+    }
+
+    fn part2(input: &Input) -> Answer {
+        Answer::from(part2(input))
+    }
+
+    /// `size` lines, either corrupted or incomplete, with an odd number of
+    /// incomplete ones.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut corrupted: Vec<bool> = (0..size).map(|_| rng.chance(1, 2)).collect();
+        if corrupted.iter().filter(|corrupted| !**corrupted).count() % 2 == 0 {
+            corrupted.push(false);
+        }
+        let mut lines = String::new();
+        for corrupted in corrupted {
+            let steps = 1 + rng.below(30);
+            let (mut line, open) = open_chunks(rng, steps);
+            if corrupted {
+                let expected = BRACKETS.iter().find(|(o, _)| *o == *open.last().unwrap()).unwrap().1;
+                let wrong: Vec<char> = BRACKETS.iter().map(|(_, c)| *c).filter(|c| *c != expected).collect();
+                line.push(*rng.choose(&wrong));
+                let steps = rng.below(5);
+                line.push_str(&open_chunks(rng, steps).0);
+            }
+            lines.push_str(&format!("{}\n", line));
+        }
+        Some(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day::solve_file;
+    use crate::differential;
+    static TEST_DATA_FILEPATH: &str = "2021/day10_test.txt";
+
+    #[test]
+    fn task1_test_data() {
+        assert_eq!(Answer::Int(26397), solve_file::<Day10>(TEST_DATA_FILEPATH, 1))
+    }
+
+    #[test]
+    fn task1() {
+        assert_eq!(Answer::Int(442131), solve_file::<Day10>(DATA_FILEPATH, 1));
+    }
+
+    #[test]
+    fn task2_test_data() {
+        assert_eq!(Answer::Int(288957), solve_file::<Day10>(TEST_DATA_FILEPATH, 2))
+    }
+
+    #[test]
+    fn task2() {
+        assert_eq!(Answer::Int(3646451424), solve_file::<Day10>(DATA_FILEPATH, 2))
+    }
+
+    /// Removes matched pairs until none is left, what remains is either
+    /// only opening brackets or starts with the corrupting closing one.
+    fn reference(lines: &Input, part: u32) -> Answer {
+        let pairs = ["()", "[]", "{}", "<>"];
+        let mut corrupted = 0;
+        let mut completions: Vec<i64> = Vec::new();
+        for line in lines {
+            let mut left = line.clone();
+            while let Some(pair) = pairs.iter().find(|pair| left.contains(*pair)) {
+                left = left.replacen(pair, "", 1);
+            }
+            match left.find([')', ']', '}', '>']) {
+                Some(idx) => corrupted += [3, 57, 1197, 25137][")]}>".find(&left[idx..=idx]).unwrap()],
+                None => completions.push(left.chars().rev().fold(0, |score, c| 5 * score + 1 + "([{<".find(c).unwrap() as i64)),
+            }
+        }
+        completions.sort();
+        Answer::from(if part == 1 { corrupted } else { completions[completions.len() / 2] })
+    }
+
+    #[test]
+    fn matches_reference() {
+        differential::check::<Day10>(1000, 15, reference)
+    }
+}
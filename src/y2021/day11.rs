@@ -0,0 +1,261 @@
+use std::cell::Cell;
+
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::input;
+use crate::input::InputError;
+use crate::rng::Rng;
+
+pub static DATA_FILEPATH: &str = "2021/day11.txt";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Octopus {
+    pub energy: Cell<i8>,
+    pub flashed: Cell<bool>,
+}
+
+pub type OctopusRow = Vec<Octopus>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid {
+    pub rows: Vec<OctopusRow>,
+}
+
+pub type Input = Grid;
+
+impl Grid {
+    fn get_octopus_at(&self, row_idx: usize, col_idx: usize) -> Option<&Octopus> {
+        let row = self.rows.get(row_idx);
+        row?;
+        let o = row.unwrap().get(col_idx);
+        o?;
+
+        Some(o.unwrap())
+    }
+}
+
+pub fn parse(raw: &str) -> Result<Input, InputError> {
+    let mut rows: Vec<OctopusRow> = Vec::new();
+    for line in input::lines(raw) {
+        let mut row: OctopusRow = Vec::new();
+        for energy in line.digits()? {
+            row.push(Octopus {
+                energy: Cell::new(energy as i8),
+                flashed: Cell::new(false),
+            })
+        }
+        rows.push(row);
+    }
+    Ok(Grid { rows })
+}
+
+fn inc_and_flash_if_needed(row_idx: usize, col_idx: usize, grid: &Grid) -> i64 {
+    let o = grid.get_octopus_at(row_idx, col_idx);
+    if o.is_none() {
+        return 0;
+    }
+
+    let o = o.unwrap();
+
+    if o.flashed.get() {
+        return 0;
+    }
+
+    if o.energy.get() < 9 {
+        o.energy.set(o.energy.get() + 1);
+        return 0;
+    }
+
+    o.flashed.set(true);
+    o.energy.set(0);
+
+    let mut flashes = 1;
+
+    if row_idx > 0 {
+        if col_idx > 0 {
+            flashes += inc_and_flash_if_needed(row_idx - 1, col_idx - 1, grid);
+        }
+        flashes += inc_and_flash_if_needed(row_idx - 1, col_idx, grid);
+        flashes += inc_and_flash_if_needed(row_idx - 1, col_idx + 1, grid);
+    }
+
+    if col_idx > 0 {
+        flashes += inc_and_flash_if_needed(row_idx, col_idx - 1, grid);
+    }
+    flashes += inc_and_flash_if_needed(row_idx, col_idx + 1, grid);
+
+    if col_idx > 0 {
+        flashes += inc_and_flash_if_needed(row_idx + 1, col_idx - 1, grid);
+    }
+    flashes += inc_and_flash_if_needed(row_idx + 1, col_idx, grid);
+    flashes += inc_and_flash_if_needed(row_idx + 1, col_idx + 1, grid);
+
+    flashes
+}
+
+fn next_step(grid: &Grid) -> i64 {
+    let mut flashes = 0;
+
+    for (row_idx, row) in grid.rows.iter().enumerate() {
+        for (col_idx, _) in row.iter().enumerate() {
+            flashes += inc_and_flash_if_needed(row_idx, col_idx, grid)
+        }
+    }
+
+    // Clear flashed flag.
+    for row in grid.rows.iter() {
+        for o in row {
+            o.flashed.set(false);
+        }
+    }
+
+    flashes
+}
+
+fn all_energy_equal_zero(grid: &Grid) -> bool {
+    let expected_energy = 0;
+
+    for row in &grid.rows {
+        for cell in row.iter() {
+            if cell.energy.get() != expected_energy {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+fn flashes_after_steps(grid: Grid, n: i64) -> i64 {
+    let mut flashes = 0;
+    for _ in 0..n {
+        flashes += next_step(&grid);
+    }
+    flashes
+}
+
+fn flashes_simultanous_after(grid: Grid) -> i64 {
+    let mut step = 0;
+    loop {
+        next_step(&grid);
+        step += 1;
+
+        if all_energy_equal_zero(&grid) {
+            return step;
+        }
+    }
+}
+
+/// Energy levels change while stepping, so each part steps its own copy.
+pub fn part1(grid: &Input) -> i64 {
+    flashes_after_steps(grid.clone(), 100)
+}
+
+pub fn part2(grid: &Input) -> i64 {
+    flashes_simultanous_after(grid.clone())
+}
+
+/// A step as the puzzle describes it: every energy goes up, then the
+/// octopuses above 9 flash in waves. Returns the number of flashes.
+fn step_energies(energies: &mut [Vec<u8>]) -> usize {
+    let (height, width) = (energies.len(), energies[0].len());
+    let mut flashing: Vec<(usize, usize)> = Vec::new();
+    for (row, col) in (0..height).flat_map(|row| (0..width).map(move |col| (row, col))) {
+        energies[row][col] += 1;
+        if energies[row][col] == 10 {
+            flashing.push((row, col));
+        }
+    }
+    let mut flashes = 0;
+    while let Some((row, col)) = flashing.pop() {
+        flashes += 1;
+        let cols = col.saturating_sub(1)..(col + 2).min(width);
+        for (r, c) in (row.saturating_sub(1)..(row + 2).min(height)).flat_map(|r| cols.clone().map(move |c| (r, c))) {
+            energies[r][c] += 1;
+            if energies[r][c] == 10 {
+                flashing.push((r, c));
+            }
+        }
+    }
+    energies.iter_mut().flatten().filter(|energy| **energy > 9).for_each(|energy| *energy = 0);
+    flashes
+}
+
+/// Step limit of generated grids, a grid not flashing all at once by then is
+/// rejected.
+static GENERATED_STEPS: usize = 1000;
+
+pub struct Day11;
+
+impl Day for Day11 {
+    type Input = Input;
+
+    fn parse(raw: &str) -> Result<Input, InputError> {
+        parse(raw)
+    }
+
+    fn part1(input: &Input) -> Answer {
+        Answer::from(part1(input))
+    }
+
+    fn part2(input: &Input) -> Answer {
+        Answer::from(part2(input))
+    }
+
+    /// A grid of up to `size + 2` octopuses a side that flashes all at once
+    /// within 1000 steps.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        loop {
+            let (width, height) = (1 + rng.below(size + 2), 1 + rng.below(size + 2));
+            let energies: Vec<Vec<u8>> = (0..height).map(|_| (0..width).map(|_| rng.below(10) as u8).collect()).collect();
+            let mut stepped = energies.clone();
+            if (0..GENERATED_STEPS).any(|_| step_energies(&mut stepped) == width * height) {
+                let rows: Vec<String> = energies.iter().map(|row| row.iter().map(|energy| format!("{}", energy)).collect()).collect();
+                return Some(rows.iter().map(|row| format!("{}\n", row)).collect());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day::solve_file;
+    use crate::differential;
+    static TEST_DATA_FILEPATH: &str = "2021/day11_test.txt";
+
+    #[test]
+    fn task1_test_data() {
+        assert_eq!(Answer::Int(1656), solve_file::<Day11>(TEST_DATA_FILEPATH, 1))
+    }
+
+    #[test]
+    fn task1() {
+        assert_eq!(Answer::Int(1571), solve_file::<Day11>(DATA_FILEPATH, 1));
+    }
+
+    #[test]
+    fn task2_test_data() {
+        assert_eq!(Answer::Int(195), solve_file::<Day11>(TEST_DATA_FILEPATH, 2))
+    }
+
+    #[test]
+    fn task2() {
+        assert_eq!(Answer::Int(387), solve_file::<Day11>(DATA_FILEPATH, 2))
+    }
+
+    fn reference(grid: &Input, part: u32) -> Answer {
+        let mut energies: Vec<Vec<u8>> = grid.rows.iter().map(|row| row.iter().map(|o| o.energy.get() as u8).collect()).collect();
+        let count = energies.len() * energies[0].len();
+        let answer = match part {
+            1 => (0..100).map(|_| step_energies(&mut energies)).sum(),
+            _ => 1 + (0..).position(|_| step_energies(&mut energies) == count).unwrap(),
+        };
+        Answer::from(answer as i64)
+    }
+
+    #[test]
+    fn matches_reference() {
+        differential::check::<Day11>(250, 8, reference)
+    }
+}
@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::input;
+use crate::input::InputError;
+use crate::rng::Rng;
+
+pub static DATA_FILEPATH: &str = "2021/day12.txt";
+
+#[derive(PartialEq, Eq, Hash)]
+enum CaveKind {
+    Big,
+    Small,
+}
+
+pub type CaveName = String;
+
+pub type CaveConnections = HashMap<CaveName, Vec<CaveName>>;
+
+pub type Input = CaveConnections;
+
+static START_CAVE: &str = "start";
+static END_CAVE: &str = "end";
+
+fn cave_kind(cave_name: &str) -> CaveKind {
+    if cave_name == cave_name.to_uppercase() {
+        CaveKind::Big
+    } else {
+        CaveKind::Small
+    }
+}
+
+pub fn parse(raw: &str) -> Result<Input, InputError> {
+    let mut connections = CaveConnections::new();
+
+    for line in input::lines(raw) {
+        let (first_cave_name, second_cave_name) = line
+            .text
+            .split_once('-')
+            .ok_or_else(|| line.missing("`-` followed by a cave name"))?;
+        for cave_name in [first_cave_name, second_cave_name] {
+            if cave_name.is_empty() || !cave_name.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(line.error(cave_name, "a cave name"));
+            }
+        }
+
+        // You can move from first cave to second cave.
+        let entry = connections
+            .entry(first_cave_name.to_string())
+            .or_default();
+        entry.push(second_cave_name.to_string());
+
+        // You can move from second cave to first cave.
+        let entry = connections.entry(second_cave_name.to_string()).or_default();
+        entry.push(first_cave_name.to_string());
+    }
+
+    Ok(connections)
+}
+
+fn find_paths(
+    start_cave: &str,
+    path: Vec<String>,
+    connections: &CaveConnections,
+    can_visit_small_twice: bool,
+) -> Vec<Vec<String>> {
+    if start_cave == END_CAVE {
+        let mut new_path = path;
+        new_path.push(END_CAVE.to_string());
+        vec![new_path]
+    } else {
+        let adjacent_caves = connections.get(start_cave).unwrap();
+        let mut paths: Vec<Vec<String>> = Vec::new();
+        for adjacent_cave in adjacent_caves {
+            let mut can_visit_small_twice = can_visit_small_twice;
+
+            if cave_kind(adjacent_cave) == CaveKind::Small && path.contains(adjacent_cave) {
+                if adjacent_cave == END_CAVE
+                    || adjacent_cave == START_CAVE
+                    || !can_visit_small_twice
+                {
+                    continue;
+                } else {
+                    can_visit_small_twice = false;
+                }
+            }
+
+            let mut new_path = path.clone();
+            new_path.push(start_cave.to_string()); // Add cave we entered on this find_paths call.
+
+            let found_paths =
+                find_paths(adjacent_cave, new_path, connections, can_visit_small_twice);
+            paths.extend(found_paths);
+        }
+
+        paths
+    }
+}
+
+pub fn part1(data: &Input) -> i64 {
+    let paths = find_paths(START_CAVE, vec![], data, false);
+    paths.len() as i64
+}
+
+pub fn part2(data: &Input) -> i64 {
+    let paths = find_paths(START_CAVE, vec![], data, true);
+    paths.len() as i64
+}
+
+pub struct Day12;
+
+impl Day for Day12 {
+    type Input = Input;
+
+    fn parse(raw: &str) -> Result<Input, InputError> {
+        parse(raw)
+    }
+
+    fn part1(input: &Input) -> Answer {
+        Answer::from(part1(input))
+    }
+
+    fn part2(input: &Input) -> Answer {
+        Answer::from(part2(input))
+    }
+
+    /// Start and end connected to a few small caves and at most two big
+    /// caves, which are never connected to each other, with up to `size + 2`
+    /// more connections.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let smalls = ["a", "b", "c", "dx", "ey", "fz"];
+        let mut caves: Vec<&str> = vec![START_CAVE, END_CAVE];
+        caves.extend(&smalls[..1 + rng.below(smalls.len())]);
+        caves.extend(&["A", "BC"][..rng.below(3)]);
+        let mut connections: Vec<(&str, &str)> = vec![
+            (START_CAVE, *rng.choose(&caves[2..])),
+            (*rng.choose(&caves[2..]), END_CAVE),
+        ];
+        let extra = rng.below(size + 3);
+        for _ in 0..100 {
+            if connections.len() >= 2 + extra {
+                break;
+            }
+            let (from, to) = (*rng.choose(&caves), *rng.choose(&caves));
+            let both_big = cave_kind(from) == CaveKind::Big && cave_kind(to) == CaveKind::Big;
+            if from != to && !both_big && !connections.contains(&(from, to)) && !connections.contains(&(to, from)) {
+                connections.push((from, to));
+            }
+        }
+        rng.shuffle(&mut connections);
+        Some(connections.iter().map(|(from, to)| format!("{}-{}\n", from, to)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day::solve_file;
+    use crate::differential;
+    static TEST_DATA_FILEPATH: &str = "2021/day12_test.txt";
+
+    #[test]
+    fn task1_test_data() {
+        assert_eq!(Answer::Int(10), solve_file::<Day12>(TEST_DATA_FILEPATH, 1))
+    }
+
+    #[test]
+    fn task1() {
+        assert_eq!(Answer::Int(5228), solve_file::<Day12>(DATA_FILEPATH, 1));
+    }
+
+    #[test]
+    fn task2_test_data() {
+        assert_eq!(Answer::Int(36), solve_file::<Day12>(TEST_DATA_FILEPATH, 2))
+    }
+
+    #[test]
+    fn task2() {
+        assert_eq!(Answer::Int(131228), solve_file::<Day12>(DATA_FILEPATH, 2))
+    }
+
+    /// Counts the paths with the visits of every small cave on the current
+    /// path.
+    fn count_paths<'a>(cave: &'a str, visits: &mut HashMap<&'a str, u32>, twice: bool, connections: &'a Input) -> i64 {
+        if cave == END_CAVE {
+            return 1;
+        }
+        let mut paths = 0;
+        for next in &connections[cave] {
+            let seen = visits.get(next.as_str()).copied().unwrap_or(0);
+            let small = next.chars().all(|c| c.is_ascii_lowercase());
+            if next == START_CAVE || (small && seen > 0 && (!twice || visits.values().any(|v| *v > 1))) {
+                continue;
+            }
+            if small {
+                visits.insert(next, seen + 1);
+            }
+            paths += count_paths(next, visits, twice, connections);
+            if small {
+                visits.insert(next, seen);
+            }
+        }
+        paths
+    }
+
+    fn reference(connections: &Input, part: u32) -> Answer {
+        Answer::from(count_paths(START_CAVE, &mut HashMap::new(), part == 2, connections))
+    }
+
+    #[test]
+    fn matches_reference() {
+        differential::check::<Day12>(1000, 8, reference)
+    }
+}
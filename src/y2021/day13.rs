@@ -0,0 +1,396 @@
+
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::input;
+use crate::input::InputError;
+use crate::rng::Rng;
+
+pub static DATA_FILEPATH: &str = "2021/day13.txt";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dot {
+    pub x: usize,
+    pub y: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Axis {
+    X,
+    Y,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fold {
+    pub axis: Axis,
+    pub position: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Input {
+    pub init_dots: Vec<Dot>,
+    pub folds: Vec<Fold>,
+    pub max_x: usize,
+    pub max_y: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum CardCordState {
+    Dot,
+    NotDot,
+}
+
+#[derive(Debug)]
+struct Card {
+    cols: Vec<Vec<CardCordState>>,
+}
+
+impl Card {
+    fn x_len(&self) -> usize {
+        self.cols.len()
+    }
+
+    fn y_len(&self) -> usize {
+        self.cols.first().expect("Card should have at least one col?").len()
+    }
+}
+
+pub fn parse(raw: &str) -> Result<Input, InputError> {
+    let mut folds_phase = false;
+    let mut init_dots: Vec<Dot> = Vec::new();
+    let mut folds: Vec<Fold> = Vec::new();
+    let mut max_x: usize = 0;
+    let mut max_y: usize = 0;
+    for line in input::lines(raw) {
+        if line.text.is_empty() {
+            folds_phase = true;
+        } else if folds_phase {
+            let fold = line
+                .text
+                .strip_prefix("fold along ")
+                .ok_or_else(|| line.error(line.text, "`fold along <axis>=<position>`"))?;
+            let (axis, position) = fold
+                .split_once('=')
+                .ok_or_else(|| line.missing("`=` followed by the fold position"))?;
+
+            folds.push(Fold {
+                axis: match axis {
+                    "x" => Axis::X,
+                    "y" => Axis::Y,
+                    _ => return Err(line.error(axis, "axis `x` or `y`")),
+                },
+                position: line.parse(position, "a number")?,
+            })
+        } else {
+            let (x, y) = line
+                .text
+                .split_once(',')
+                .ok_or_else(|| line.missing("`,` followed by y"))?;
+            let x: usize = line.parse(x, "a number")?;
+            let y: usize = line.parse(y, "a number")?;
+
+            if x > max_x {
+                max_x = x;
+            }
+
+            if y > max_y {
+                max_y = y;
+            }
+
+            init_dots.push(Dot { x, y })
+        }
+    }
+    Ok(Input { init_dots, folds, max_x, max_y })
+}
+
+fn create_card(max_x: usize, max_y: usize, init_dots: &[Dot]) -> Card {
+    let mut cols: Vec<Vec<CardCordState>> = Vec::new();
+    for _ in 0..max_x+1 {
+        let mut col: Vec<CardCordState> = Vec::new();
+        for _ in 0..max_y+1 {
+            col.push(CardCordState::NotDot);
+        };
+        cols.push(col);
+    };
+
+    for init_dot in init_dots {
+        let col = cols.get_mut(init_dot.x).expect("Invalid x in init_dots.");
+        col[init_dot.y] = CardCordState::Dot;
+    }
+
+    Card {
+        cols
+    }
+}
+
+fn build_folded_x(card: Card, position: u32) -> Card {
+    let first_chunk = &card.cols[..position as usize].to_vec(); // from 0 to pos - 1
+    let second_chunk = &card.cols[position as usize + 1..].to_vec(); // from pos + 1, to end
+
+    let chunks = if first_chunk.len() < second_chunk.len() {
+        (second_chunk, first_chunk)
+    } else {
+        (first_chunk, second_chunk)
+    };
+
+    let mut bigger_chunk = chunks.0.to_vec();
+    let smaller_chunk = chunks.1.to_vec();
+
+    let mut bigger_idx_to_apply = bigger_chunk.len();
+    for col in smaller_chunk {
+        bigger_idx_to_apply -= 1;
+        for (idx, cell) in col.iter().enumerate() {
+            if *cell == CardCordState::Dot {
+                bigger_chunk[bigger_idx_to_apply][idx] = CardCordState::Dot;
+            }
+        }
+    }
+
+    Card {
+        cols: bigger_chunk.to_vec()
+    }
+}
+
+fn build_folded_y(card: Card, position: u32) -> Card {
+    let mut new_cols: Vec<Vec<CardCordState>> = Vec::new();
+    for col in card.cols {
+        let first_chunk = col[..position as usize].to_vec(); // from 0 to pos - 1
+        let second_chunk = col[position as usize + 1..].to_vec(); // from pos + 1, to end
+
+        let chunks = if first_chunk.len() < second_chunk.len() {
+            (second_chunk, first_chunk)
+        } else {
+            (first_chunk, second_chunk)
+        };
+
+        let mut bigger_chunk = chunks.0;
+        let smaller_chunk = chunks.1;
+
+        let mut bigger_idx_to_apply = bigger_chunk.len();
+        for cell in smaller_chunk.iter() {
+            bigger_idx_to_apply -= 1;
+            if *cell == CardCordState::Dot {
+                bigger_chunk[bigger_idx_to_apply] = CardCordState::Dot;
+            }
+        };
+
+        new_cols.push(bigger_chunk.to_vec());
+    }
+
+    Card {
+        cols: new_cols
+    }
+}
+
+fn apply_folds(card: Card, folds: &[Fold]) -> Card {
+    if folds.is_empty() {
+        return card;
+    }
+
+    let mut final_card = card;
+
+    for fold in folds {
+        match fold.axis {
+            Axis::X => {
+                // panic!("Not implemented");
+                final_card = build_folded_x(final_card, fold.position);
+            },
+            Axis::Y => {
+                final_card = build_folded_y(final_card, fold.position);
+            }
+        };
+    }
+
+    // TODO: return new card, folded
+    final_card
+}
+
+fn count_dots(card: Card) -> i64 {
+    let mut count = 0;
+    for col in card.cols {
+        for cell in col {
+            if cell == CardCordState::Dot {
+                count += 1;
+            }
+        }
+    };
+    count
+}
+
+fn render_card(card: Card) -> Vec<String> {
+    let mut rows: Vec<String> = Vec::new();
+    for y_idx in 0..card.y_len() {
+        let mut row = String::new();
+        for x_idx in 0..card.x_len() {
+            let value = &card.cols[x_idx][y_idx];
+            if value == &CardCordState::Dot {
+                row.push('#')
+            } else {
+                row.push('.')
+            }
+        }
+        rows.push(row)
+    }
+    rows
+}
+
+pub fn part1(input: &Input) -> i64 {
+    let card = create_card(input.max_x, input.max_y, &input.init_dots);
+    let card = apply_folds(card, &input.folds[..1]);
+    count_dots(card)
+}
+
+/// The folded card, one string of `#` and `.` per row.
+pub fn part2(input: &Input) -> Vec<String> {
+    let card = create_card(input.max_x, input.max_y, &input.init_dots);
+    let card = apply_folds(card, &input.folds[..]);
+    render_card(card)
+}
+
+pub struct Day13;
+
+impl Day for Day13 {
+    type Input = Input;
+
+    fn parse(raw: &str) -> Result<Input, InputError> {
+        parse(raw)
+    }
+
+    fn part1(input: &Input) -> Answer {
+        Answer::from(part1(input))
+    }
+
+    fn part2(input: &Input) -> Answer {
+        Answer::Grid(part2(input))
+    }
+
+    /// Up to `3 * size` dots on a card folded in half up to twice along each
+    /// axis, with no dot on a fold line.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut axes: Vec<Axis> = [Axis::X, Axis::Y].iter().flat_map(|axis| vec![*axis; rng.below(3)]).collect();
+        if axes.is_empty() {
+            axes.push(*rng.choose(&[Axis::X, Axis::Y]));
+        }
+        rng.shuffle(&mut axes);
+        // Folding a side of 2p + 1 along p leaves p.
+        let folded = [1 + rng.below(size + 4), 1 + rng.below(size + 4)];
+        let mut sides = folded;
+        let mut folds: Vec<Fold> = Vec::new();
+        for axis in axes.iter().rev() {
+            let side = &mut sides[*axis as usize];
+            folds.insert(0, Fold { axis: *axis, position: *side as u32 });
+            *side = 2 * *side + 1;
+        }
+        // Unfolding from the folded card keeps dots off the fold lines.
+        let unfold = |rng: &mut Rng, axis: Axis, mut coord: usize| {
+            for fold in folds.iter().rev().filter(|fold| fold.axis == axis) {
+                if rng.chance(1, 2) {
+                    coord = 2 * fold.position as usize - coord;
+                }
+            }
+            coord
+        };
+        let [width, height] = sides;
+        let mut dots: Vec<(usize, usize)> = Vec::new();
+        for _ in 0..1 + rng.below(3 * size) {
+            let (x, y) = (rng.below(folded[0]), rng.below(folded[1]));
+            dots.push((unfold(rng, Axis::X, x), unfold(rng, Axis::Y, y)));
+        }
+        // The card spans up to the furthest dots.
+        dots.push((width - 1, dots[0].1));
+        dots.push((dots[0].0, height - 1));
+        dots.sort();
+        dots.dedup();
+        rng.shuffle(&mut dots);
+        let dots: Vec<String> = dots.iter().map(|(x, y)| format!("{},{}\n", x, y)).collect();
+        let folds: Vec<String> = folds
+            .iter()
+            .map(|fold| format!("fold along {}={}\n", if fold.axis == Axis::X { "x" } else { "y" }, fold.position))
+            .collect();
+        Some(format!("{}\n{}", dots.concat(), folds.concat()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day::solve_file;
+    use crate::differential;
+    static TEST_DATA_FILEPATH: &str = "2021/day13_test.txt";
+
+    #[test]
+    fn task1_test_data() {
+        assert_eq!(Answer::Int(17), solve_file::<Day13>(TEST_DATA_FILEPATH, 1))
+    }
+
+    #[test]
+    fn task1() {
+        assert_eq!(Answer::Int(653), solve_file::<Day13>(DATA_FILEPATH, 1));
+    }
+
+    #[test]
+    fn task2_test_data() {
+        let expected = Answer::Grid(vec![
+            String::from("#####"),
+            String::from("#...#"),
+            String::from("#...#"),
+            String::from("#...#"),
+            String::from("#####"),
+            String::from("....."),
+            String::from("....."),
+        ]);
+        assert_eq!(expected, solve_file::<Day13>(TEST_DATA_FILEPATH, 2))
+    }
+
+    #[test]
+    fn task2() {
+        // Reads as "LKREBPRK".
+        let expected = Answer::Grid(vec![
+            String::from("#....#..#.###..####.###..###..###..#..#."),
+            String::from("#....#.#..#..#.#....#..#.#..#.#..#.#.#.."),
+            String::from("#....##...#..#.###..###..#..#.#..#.##..."),
+            String::from("#....#.#..###..#....#..#.###..###..#.#.."),
+            String::from("#....#.#..#.#..#....#..#.#....#.#..#.#.."),
+            String::from("####.#..#.#..#.####.###..#....#..#.#..#."),
+        ]);
+        assert_eq!(expected, solve_file::<Day13>(DATA_FILEPATH, 2))
+    }
+
+    /// Reflects every dot across the folds and draws the dots left.
+    fn reference(input: &Input, part: u32) -> Answer {
+        let folds = if part == 1 { &input.folds[..1] } else { &input.folds[..] };
+        let (mut width, mut height) = (input.max_x + 1, input.max_y + 1);
+        let mut dots: Vec<(usize, usize)> = input.init_dots.iter().map(|dot| (dot.x, dot.y)).collect();
+        for fold in folds {
+            let position = fold.position as usize;
+            let reflect = |coord: usize| if coord > position { 2 * position - coord } else { coord };
+            match fold.axis {
+                Axis::X => {
+                    dots = dots.into_iter().map(|(x, y)| (reflect(x), y)).collect();
+                    width = position;
+                }
+                Axis::Y => {
+                    dots = dots.into_iter().map(|(x, y)| (x, reflect(y))).collect();
+                    height = position;
+                }
+            }
+        }
+        dots.sort();
+        dots.dedup();
+        if part == 1 {
+            return Answer::from(dots.len() as i64);
+        }
+        let rows = (0..height).map(|y| (0..width).map(|x| if dots.contains(&(x, y)) { '#' } else { '.' }).collect());
+        Answer::Grid(rows.collect())
+    }
+
+    #[test]
+    fn matches_reference() {
+        differential::check::<Day13>(1000, 8, reference)
+    }
+
+    #[test]
+    fn parse_reports_bad_axis() {
+        let err = parse("6,10\n\nfold along z=7").unwrap_err();
+        assert_eq!((3, 12, "`z`"), (err.line, err.column, err.found.as_str()));
+    }
+}
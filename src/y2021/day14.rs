@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::input;
+use crate::input::InputError;
+use crate::rng::Rng;
+
+pub static DATA_FILEPATH: &str = "2021/day14.txt";
+
+type Polymer = HashMap<String, i64>;
+
+/// Pair to the two pairs it turns into, e.g. `CH -> B` is `CH: [CB, BH]`.
+pub type Insertions = HashMap<String, Vec<String>>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Input {
+    pub template: String,
+    pub insertions: Insertions,
+}
+
+pub fn parse(raw: &str) -> Result<Input, InputError> {
+    let mut lines = input::lines(raw);
+    let template_line = lines
+        .next()
+        .ok_or_else(|| input::end_of_input(raw, "a polymer template"))?;
+    if template_line.text.chars().count() < 2 {
+        return Err(template_line.missing("a template of at least two elements"));
+    }
+    let template = template_line.text.to_string();
+    if let Some(line) = lines.next().filter(|line| !line.text.is_empty()) {
+        return Err(line.error(line.text, "a blank line after the template"));
+    }
+    let mut insertions: Insertions = HashMap::new();
+    for line in lines {
+        let (from, to) = line
+            .text
+            .split_once(" -> ")
+            .ok_or_else(|| line.missing("` -> ` followed by the inserted element"))?;
+        let mut chars = from.chars();
+        let (first_char, second_char) = match (chars.next(), chars.next(), chars.next()) {
+            (Some(first_char), Some(second_char), None) => (first_char, second_char),
+            _ => return Err(line.error(from, "a pair of elements")),
+        };
+        if to.chars().count() != 1 {
+            return Err(line.error(to, "a single element"));
+        }
+        insertions.insert(
+            from.to_string(),
+            vec![format!("{}{}", first_char, to), format!("{}{}", to, second_char)],
+        );
+    }
+
+    Ok(Input {
+        template,
+        insertions,
+    })
+}
+
+fn group_in_pairs(value: &str) -> Vec<String> {
+    let mut idx = 0;
+    let max_idx = value.len() - 2;
+
+    let mut result = Vec::new();
+
+    while idx <= max_idx {
+        result.push(value[idx..=idx + 1].to_string());
+        idx += 1;
+    }
+
+    result
+}
+
+fn run_step(polymer: Polymer, insertions: &Insertions) -> Polymer {
+    let mut new_polymer = Polymer::new();
+
+    for (pair, count) in polymer.iter() {
+        match insertions.get(pair) {
+            Some(new_pairs) => {
+                for new_pair in new_pairs {
+                    let entry = new_polymer.entry(new_pair.to_string()).or_insert(0);
+                    *entry += count
+                }
+            }
+            _ => {
+                let entry = new_polymer.entry(pair.to_string()).or_insert(0);
+                *entry += count
+            }
+        }
+    }
+
+    new_polymer
+}
+
+fn polymer_from_template(template: &str) -> Polymer {
+    let mut polymer = Polymer::new();
+    for e in group_in_pairs(template) {
+        let entry = polymer.entry(e).or_insert(0);
+        *entry += 1;
+    }
+    polymer
+}
+
+fn polymer_after(data: &Input, steps: i64) -> Polymer {
+    let mut polymer = polymer_from_template(&data.template);
+
+    for _ in 1..=steps {
+        polymer = run_step(polymer, &data.insertions);
+    }
+
+    polymer
+}
+
+/// Difference between the most and least common element after `steps`.
+pub fn run_steps(data: &Input, steps: i64) -> i64 {
+    let polymer = polymer_after(data, steps);
+
+    // Every element starts a pair except the last one, which insertions
+    // never move.
+    let mut counts = HashMap::<char, i64>::new();
+    for (pair, count) in polymer.iter() {
+        let entry = counts.entry(pair.chars().next().unwrap()).or_insert(0);
+        *entry += count;
+    }
+    *counts.entry(data.template.chars().last().unwrap()).or_insert(0) += 1;
+
+    let mut sorted_counts: Vec<i64> = counts.into_values().collect();
+    sorted_counts.sort();
+
+    let smallest_count = sorted_counts.first().unwrap();
+    let biggest_count = sorted_counts.last().unwrap();
+
+    biggest_count - smallest_count
+}
+
+pub fn part1(data: &Input) -> i64 {
+    run_steps(data, 10)
+}
+
+pub fn part2(data: &Input) -> i64 {
+    run_steps(data, 40)
+}
+
+/// Pair counts after `steps`, sorted by pair, and the resulting difference.
+fn show_step(data: &Input, steps: &str) -> Result<String, String> {
+    let steps: i64 = steps
+        .parse()
+        .ok()
+        .filter(|steps| *steps >= 0)
+        .ok_or_else(|| format!("Invalid number of steps: {}", steps))?;
+    let mut pairs: Vec<(String, i64)> = polymer_after(data, steps).into_iter().collect();
+    pairs.sort();
+    let mut lines: Vec<String> = pairs.iter().map(|(pair, count)| format!("{} {}", pair, count)).collect();
+    lines.push(format!("most - least common: {}", run_steps(data, steps)));
+    Ok(lines.join("\n"))
+}
+
+pub struct Day14;
+
+impl Day for Day14 {
+    type Input = Input;
+
+    const COMMANDS: &'static [&'static str] = &["step <n>        pair counts after n steps"];
+
+    fn parse(raw: &str) -> Result<Input, InputError> {
+        parse(raw)
+    }
+
+    fn part1(input: &Input) -> Answer {
+        Answer::from(part1(input))
+    }
+
+    fn part2(input: &Input) -> Answer {
+        Answer::from(part2(input))
+    }
+
+    /// A template of up to `size + 2` elements and a rule for every pair of
+    /// up to six elements.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let elements: Vec<char> = "BCHNOS".chars().take(2 + rng.below(5)).collect();
+        let template: String = (0..2 + rng.below(size + 1)).map(|_| *rng.choose(&elements)).collect();
+        let mut rules: Vec<String> = elements
+            .iter()
+            .flat_map(|first| elements.iter().map(move |second| (*first, *second)))
+            .map(|(first, second)| format!("{}{} -> {}\n", first, second, rng.choose(&elements)))
+            .collect();
+        rng.shuffle(&mut rules);
+        Some(format!("{}\n\n{}", template, rules.concat()))
+    }
+
+    fn inspect(input: &Input, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        match (command, args) {
+            ("step", [steps]) => Some(show_step(input, steps)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day::solve_file;
+    use crate::differential;
+    static TEST_DATA_FILEPATH: &str = "2021/day14_test.txt";
+
+    #[test]
+    fn task1_test_data() {
+        assert_eq!(Answer::Int(1588), solve_file::<Day14>(TEST_DATA_FILEPATH, 1))
+    }
+
+    #[test]
+    fn task1() {
+        assert_eq!(Answer::Int(2587), solve_file::<Day14>(DATA_FILEPATH, 1));
+    }
+
+    #[test]
+    fn task2_test_data() {
+        assert_eq!(Answer::Int(2188189693529), solve_file::<Day14>(TEST_DATA_FILEPATH, 2))
+    }
+
+    #[test]
+    fn task2() {
+        assert_eq!(Answer::Int(3318837563123), solve_file::<Day14>(DATA_FILEPATH, 2))
+    }
+
+    #[test]
+    fn test_run_step() {
+        assert_eq!(1/2, 0);
+        let insertions = Insertions::from([
+            (
+                String::from("AB"),
+                vec![String::from("AC"), String::from("CB")],
+            ),
+            (
+                String::from("DE"),
+                vec![String::from("DC"), String::from("CE")],
+            ),
+        ]);
+        let polymer = Polymer::from([(String::from("AB"), 2), (String::from("AC"), 2)]);
+        let expected = Polymer::from([(String::from("AC"), 4), (String::from("CB"), 2)]);
+
+        assert_eq!(expected, run_step(polymer, &insertions));
+    }
+
+    /// Grows the polymer itself for 10 steps, for 40 counts the elements each
+    /// pair gains per depth.
+    fn reference(data: &Input, part: u32) -> Answer {
+        let rule = |first: char, second: char| data.insertions[&format!("{}{}", first, second)][0].chars().nth(1).unwrap();
+        let mut counts: HashMap<char, i64> = HashMap::new();
+        if part == 1 {
+            let mut polymer: Vec<char> = data.template.chars().collect();
+            for _ in 0..10 {
+                let inserted = polymer.windows(2).map(|pair| rule(pair[0], pair[1]));
+                let mut grown: Vec<char> = polymer.iter().zip(inserted).flat_map(|(kept, inserted)| [*kept, inserted]).collect();
+                grown.push(*polymer.last().unwrap());
+                polymer = grown;
+            }
+            polymer.iter().for_each(|element| *counts.entry(*element).or_insert(0) += 1);
+        } else {
+            type Gained = HashMap<(char, char, u32), HashMap<char, i64>>;
+            fn gained(first: char, second: char, depth: u32, rule: &dyn Fn(char, char) -> char, memo: &mut Gained) -> HashMap<char, i64> {
+                if depth == 0 {
+                    return HashMap::new();
+                }
+                if let Some(counts) = memo.get(&(first, second, depth)) {
+                    return counts.clone();
+                }
+                let middle = rule(first, second);
+                let mut counts = gained(first, middle, depth - 1, rule, memo);
+                for (element, count) in gained(middle, second, depth - 1, rule, memo) {
+                    *counts.entry(element).or_insert(0) += count;
+                }
+                *counts.entry(middle).or_insert(0) += 1;
+                memo.insert((first, second, depth), counts.clone());
+                counts
+            }
+            let template: Vec<char> = data.template.chars().collect();
+            let mut memo = Gained::new();
+            template.iter().for_each(|element| *counts.entry(*element).or_insert(0) += 1);
+            for pair in template.windows(2) {
+                for (element, count) in gained(pair[0], pair[1], 40, &rule, &mut memo) {
+                    *counts.entry(element).or_insert(0) += count;
+                }
+            }
+        }
+        Answer::from(counts.values().max().unwrap() - counts.values().min().unwrap())
+    }
+
+    #[test]
+    fn matches_reference() {
+        differential::check::<Day14>(150, 10, reference)
+    }
+
+    #[test]
+    fn counts_template_starting_and_ending_alike() {
+        let input = parse("NBN\n\nNB -> N\nBN -> B\nNN -> B\nBB -> N\n").unwrap();
+        // NBN, NNBBN
+        assert_eq!(1, run_steps(&input, 0));
+        assert_eq!(1, run_steps(&input, 1));
+    }
+
+    #[test]
+    fn parse_reports_missing_blank_line() {
+        let err = parse("NNCB\nCH -> B\n").unwrap_err();
+        assert_eq!((2, 1, "`CH -> B`"), (err.line, err.column, err.found.as_str()));
+    }
+
+    #[test]
+    fn inspect_step() {
+        let input = parse("NNCB\n\nCH -> B\nNN -> C\nNC -> B\nCB -> H\n").unwrap();
+        let expected = "CB 1\nNC 1\nNN 1\nmost - least common: 1";
+        assert_eq!(Some(Ok(String::from(expected))), Day14::inspect(&input, "step", &["0"]));
+        assert!(Day14::inspect(&input, "step", &["-1"]).unwrap().is_err());
+    }
+}
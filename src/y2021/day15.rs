@@ -0,0 +1,267 @@
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::day::Variant;
+use crate::debug;
+use crate::input;
+use crate::input::InputError;
+use crate::rng::Rng;
+
+pub static DATA_FILEPATH: &str = "2021/day15.txt";
+
+pub type Row = Vec<i64>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cave {
+    pub rows: Vec<Row>,
+}
+
+pub type Input = Cave;
+
+impl Cave {
+    fn get_risk(&self, row_idx: usize, col_idx: usize) -> Option<i64> {
+        match self.rows.get(row_idx) {
+            Some(row) => row.get(col_idx).copied(),
+            _ => None,
+        }
+    }
+
+    fn rows_len(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn cols_len(&self) -> usize {
+        self.rows.first().unwrap().len()
+    }
+}
+
+pub fn parse(raw: &str) -> Result<Input, InputError> {
+    let mut rows = Vec::new();
+    for line in input::lines(raw) {
+        let row: Row = line.digits()?.into_iter().map(i64::from).collect();
+        rows.push(row);
+    }
+    Ok(Cave { rows })
+}
+
+/// The full map is the cave tiled 5x5 times, with risk increasing by one for
+/// every tile to the right or down and wrapping around from 9 back to 1.
+fn expand(cave: &Cave) -> Cave {
+    let rows = (0..5 * cave.rows_len())
+        .map(|row_idx| {
+            (0..5 * cave.cols_len())
+                .map(|col_idx| {
+                    let tile = (row_idx / cave.rows_len() + col_idx / cave.cols_len()) as i64;
+                    let risk = cave
+                        .get_risk(row_idx % cave.rows_len(), col_idx % cave.cols_len())
+                        .unwrap();
+                    (risk + tile - 1) % 9 + 1
+                })
+                .collect()
+        })
+        .collect();
+    Cave { rows }
+}
+
+/// Only moves right and down, which misses paths that wind back up or left,
+/// and pays the risk of each position when leaving it rather than entering it.
+fn find_least_risky_right_down(cave: &Cave) -> i64 {
+    let mut rows: Vec<Row> = vec![vec![0; cave.cols_len()]; cave.rows_len()];
+
+    for row_idx in 0..cave.rows_len() {
+        for col_idx in 0..cave.cols_len() {
+            let mut options: Vec<i64> = Vec::new();
+            if col_idx > 0 {
+                let risk_to_leave_left = cave.get_risk(row_idx, col_idx - 1).unwrap();
+                let risk_to_get_to_the_left = rows[row_idx][col_idx - 1];
+                options.push(risk_to_get_to_the_left + risk_to_leave_left);
+            }
+
+            if row_idx > 0 {
+                let risk_to_leave_top = cave.get_risk(row_idx - 1, col_idx).unwrap();
+                let risk_to_get_to_top = rows[row_idx - 1][col_idx];
+                options.push(risk_to_get_to_top + risk_to_leave_top);
+            }
+
+            options.sort();
+
+            // if (options.len() == 2 && options.first().unwrap() == options.last().unwrap()) {
+            //     options.reverse();
+            // }
+
+            let risk_to_get_to_this = options.first();
+
+            if let Some(risk_to_get_to_this) = risk_to_get_to_this {
+                rows[row_idx][col_idx] = *risk_to_get_to_this;
+            }
+        }
+    }
+
+    debug!("risk rows={:?}", rows);
+
+    let last_row = rows.last().unwrap();
+
+    let risk_to_get_to_last = last_row.last().unwrap();
+
+    *risk_to_get_to_last
+}
+
+/// Dijkstra over the whole cave, moving in all four directions.
+fn find_least_risky(cave: &Cave) -> i64 {
+    let (rows_len, cols_len) = (cave.rows_len(), cave.cols_len());
+    let mut risks = vec![vec![i64::MAX; cols_len]; rows_len];
+    let mut queue = BinaryHeap::from([Reverse((0, 0, 0))]);
+    risks[0][0] = 0;
+    while let Some(Reverse((risk, row_idx, col_idx))) = queue.pop() {
+        if (row_idx, col_idx) == (rows_len - 1, cols_len - 1) {
+            return risk;
+        }
+        if risk > risks[row_idx][col_idx] {
+            continue;
+        }
+        let neighbours = [
+            (row_idx.wrapping_sub(1), col_idx),
+            (row_idx + 1, col_idx),
+            (row_idx, col_idx.wrapping_sub(1)),
+            (row_idx, col_idx + 1),
+        ];
+        for (next_row, next_col) in neighbours {
+            if let Some(risk_to_enter) = cave.get_risk(next_row, next_col) {
+                let next_risk = risk + risk_to_enter;
+                if next_risk < risks[next_row][next_col] {
+                    risks[next_row][next_col] = next_risk;
+                    queue.push(Reverse((next_risk, next_row, next_col)));
+                }
+            }
+        }
+    }
+    panic!("No path to the bottom right of the cave")
+}
+
+pub fn part1(cave: &Input) -> i64 {
+    find_least_risky(cave)
+}
+
+pub fn part2(cave: &Input) -> i64 {
+    find_least_risky(&expand(cave))
+}
+
+pub struct Day15;
+
+impl Day for Day15 {
+    type Input = Input;
+
+    fn parse(raw: &str) -> Result<Input, InputError> {
+        parse(raw)
+    }
+
+    fn part1(input: &Input) -> Answer {
+        Answer::from(part1(input))
+    }
+
+    fn part2(input: &Input) -> Answer {
+        Answer::from(part2(input))
+    }
+
+    fn variants() -> Vec<Variant<Input>> {
+        vec![
+            Variant {
+                part: 1,
+                name: "right-down",
+                solve: |cave| Answer::from(find_least_risky_right_down(cave)),
+            },
+            Variant {
+                part: 2,
+                name: "right-down",
+                solve: |cave| Answer::from(find_least_risky_right_down(&expand(cave))),
+            },
+        ]
+    }
+
+    /// A cave of up to `size + 1` positions a side.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let (width, height) = (1 + rng.below(size + 1), 1 + rng.below(size + 1));
+        let rows: Vec<String> = (0..height)
+            .map(|_| (0..width).map(|_| rng.range(1..10).to_string()).collect::<String>() + "\n")
+            .collect();
+        Some(rows.concat())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day::solve_file;
+    use crate::differential;
+    static TEST_DATA_FILEPATH: &str = "2021/day15_test.txt";
+
+    #[test]
+    fn task1_test_data() {
+        assert_eq!(Answer::Int(40), solve_file::<Day15>(TEST_DATA_FILEPATH, 1))
+    }
+
+    #[test]
+    fn task1() {
+        assert_eq!(Answer::Int(363), solve_file::<Day15>(DATA_FILEPATH, 1));
+    }
+
+    #[test]
+    fn task2_test_data() {
+        assert_eq!(Answer::Int(315), solve_file::<Day15>(TEST_DATA_FILEPATH, 2))
+    }
+
+    #[test]
+    fn task2() {
+        assert_eq!(Answer::Int(2835), solve_file::<Day15>(DATA_FILEPATH, 2))
+    }
+
+    #[test]
+    fn moves_up_and_left() {
+        let cave = parse("19111\n11191\n99991\n").unwrap();
+        assert_eq!(14, find_least_risky_right_down(&cave));
+        assert_eq!(8, find_least_risky(&cave));
+    }
+
+    /// Lowers the risk of every position from its neighbours until nothing
+    /// changes.
+    fn reference(cave: &Input, part: u32) -> Answer {
+        let cave = if part == 1 { cave.clone() } else { expand(cave) };
+        let (rows_len, cols_len) = (cave.rows_len(), cave.cols_len());
+        let mut risks = vec![vec![i64::MAX; cols_len]; rows_len];
+        risks[0][0] = 0;
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (row_idx, col_idx) in (0..rows_len).flat_map(|row_idx| (0..cols_len).map(move |col_idx| (row_idx, col_idx))) {
+                let neighbours = [
+                    (row_idx.wrapping_sub(1), col_idx),
+                    (row_idx + 1, col_idx),
+                    (row_idx, col_idx.wrapping_sub(1)),
+                    (row_idx, col_idx + 1),
+                ];
+                for (next_row, next_col) in neighbours {
+                    if next_row < rows_len && next_col < cols_len && risks[next_row][next_col] != i64::MAX {
+                        let risk = risks[next_row][next_col] + cave.rows[row_idx][col_idx];
+                        if risk < risks[row_idx][col_idx] {
+                            risks[row_idx][col_idx] = risk;
+                            changed = true;
+                        }
+                    }
+                }
+            }
+        }
+        Answer::from(risks[rows_len - 1][cols_len - 1])
+    }
+
+    #[test]
+    fn matches_reference() {
+        let found = differential::mismatches::<Day15>(500, 8, reference);
+        // The right-down variants miss winding paths, see `moves_up_and_left`.
+        let report: Vec<String> = found.iter().filter(|mismatch| mismatch.solver != "right-down").map(|m| m.to_string()).collect();
+        assert!(report.is_empty(), "{}", report.join("\n"));
+        assert!(!found.is_empty());
+    }
+}
@@ -1,7 +1,10 @@
-use std::{fmt, ops::Add};
-use std::convert::TryFrom;
+use std::fmt;
 
 use crate::hex::{hex_str_to_bin, bin_str_to_u32};
+use crate::input;
+use crate::input::InputError;
+
+pub static DATA_FILEPATH: &str = "2021/day16.txt";
 
 // packet
 // first three bits packet version <-- most sign bits first
@@ -25,14 +28,14 @@ struct Packet{
 
 #[derive(PartialEq, Debug)]
 enum PacketType {
-    Literal = 6,
+    Literal = 4,
     Other,
 }
 
 impl From<u32> for PacketType {
     fn from(v: u32) -> Self {
         match v {
-            6 => PacketType::Literal,
+            4 => PacketType::Literal,
             _ => PacketType::Other,
         }
     }
@@ -53,7 +56,7 @@ fn value_literal_to_u32(value: String) -> u32 {
     loop {
         let chunk = &value[start..start+5];
         let chunk_bin_value = &chunk[1..];
-        bin_val.push_str(chunk_bin_value.into());
+        bin_val.push_str(chunk_bin_value);
 
         if chunk.starts_with("0") {
             break
@@ -106,7 +109,7 @@ impl Packet {
         if self.packet_type() == PacketType::Literal {
             Err("packet that is literal has no length type id")
         } else {
-            Ok(self.raw_content[..0].into())
+            Ok(self.raw_content[..1].into())
         }
     }
 
@@ -119,12 +122,21 @@ impl Packet {
     }
 }
 
-impl<'a> fmt::Display for Packet {
+impl fmt::Display for Packet {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "({}, {}, {})", self.raw_version, self.raw_type, self.raw_content)
     }
 }
 
+/// Reads the hexadecimal transmission. The day implements no `Day` until a
+/// part is solved.
+pub fn parse(raw: &str) -> Result<String, InputError> {
+    let line = input::lines(raw)
+        .next()
+        .ok_or_else(|| input::end_of_input(raw, "a hexadecimal transmission"))?;
+    line.check_chars(|c| c.is_ascii_hexdigit(), "a hexadecimal digit")?;
+    Ok(line.text.to_string())
+}
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,186 @@
+
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::input;
+use crate::input::InputError;
+use crate::rng::Rng;
+
+pub static DATA_FILEPATH: &str = "2021/day2.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoatDirection {
+    Forward,
+    Up,
+    Down,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoatCommand {
+    pub direction: BoatDirection,
+    pub distance: i64,
+}
+
+pub type Input = Vec<BoatCommand>;
+
+#[derive(Debug)]
+struct BoatPosition {
+    horizontal_position: i64,
+    depth: i64,
+}
+
+pub fn parse(raw: &str) -> Result<Input, InputError> {
+    input::lines(raw)
+        .map(|line| {
+            let mut split = line.text.split(' ');
+            let direction = line.field(split.next(), "a direction")?;
+            let direction = match direction {
+                "up" => BoatDirection::Up,
+                "down" => BoatDirection::Down,
+                "forward" => BoatDirection::Forward,
+                _ => return Err(line.error(direction, "`up`, `down` or `forward`")),
+            };
+            let distance = line.parse(line.field(split.next(), "a distance")?, "a number")?;
+            line.end(split.next())?;
+            Ok(BoatCommand { direction, distance })
+        })
+        .collect()
+}
+
+fn final_position(commands: &[BoatCommand]) -> BoatPosition {
+    let mut horizontal_position = 0;
+    let mut depth = 0;
+
+    for command in commands {
+        match command.direction {
+            BoatDirection::Up => depth -= command.distance,
+            BoatDirection::Down => depth += command.distance,
+            BoatDirection::Forward => horizontal_position += command.distance,
+        }
+    }
+
+    BoatPosition {
+        horizontal_position,
+        depth,
+    }
+}
+
+fn final_position_with_aim(commands: &[BoatCommand]) -> BoatPosition {
+    let mut horizontal_position = 0;
+    let mut depth = 0;
+    let mut aim = 0;
+
+    for command in commands {
+        match command.direction {
+            BoatDirection::Up => aim -= command.distance,
+            BoatDirection::Down => aim += command.distance,
+            BoatDirection::Forward => {
+                horizontal_position += command.distance;
+                depth += aim * command.distance;
+            }
+        }
+    }
+
+    BoatPosition {
+        horizontal_position,
+        depth,
+    }
+}
+
+pub fn part1(commands: &Input) -> i64 {
+    let position = final_position(commands);
+    position.depth * position.horizontal_position
+}
+
+pub fn part2(commands: &Input) -> i64 {
+    let position = final_position_with_aim(commands);
+    position.depth * position.horizontal_position
+}
+
+pub struct Day2;
+
+impl Day for Day2 {
+    type Input = Input;
+
+    fn parse(raw: &str) -> Result<Input, InputError> {
+        parse(raw)
+    }
+
+    fn part1(input: &Input) -> Answer {
+        Answer::from(part1(input))
+    }
+
+    fn part2(input: &Input) -> Answer {
+        Answer::from(part2(input))
+    }
+
+    /// `size` commands moving at most 9 units.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let directions = ["forward", "forward", "down", "up"];
+        let commands: Vec<String> = (0..size)
+            .map(|_| format!("{} {}\n", rng.choose(&directions), rng.range(1..10)))
+            .collect();
+        Some(commands.concat())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day::solve_file;
+    use crate::differential;
+    static TEST_DATA_FILEPATH: &str = "2021/day2_test.txt";
+
+    #[test]
+    fn task1_test_data() {
+        assert_eq!(Answer::Int(150), solve_file::<Day2>(TEST_DATA_FILEPATH, 1))
+    }
+
+    #[test]
+    fn task1() {
+        assert_eq!(Answer::Int(2322630), solve_file::<Day2>(DATA_FILEPATH, 1))
+    }
+
+    #[test]
+    fn task2_test_data() {
+        assert_eq!(Answer::Int(900), solve_file::<Day2>(TEST_DATA_FILEPATH, 2))
+    }
+
+    #[test]
+    fn task2() {
+        assert_eq!(Answer::Int(2105273490), solve_file::<Day2>(DATA_FILEPATH, 2))
+    }
+
+    #[test]
+    fn parse_reports_bad_direction() {
+        let err = parse("forward 5\nsideways 3").unwrap_err();
+        assert_eq!((2, 1, "`sideways`"), (err.line, err.column, err.found.as_str()));
+        let err = parse("up x").unwrap_err();
+        assert_eq!((1, 4), (err.line, err.column));
+    }
+
+    /// Moves one unit at a time.
+    fn reference(commands: &Input, part: u32) -> Answer {
+        let (mut horizontal, mut depth, mut aim) = (0, 0, 0);
+        for command in commands {
+            for _ in 0..command.distance {
+                match (command.direction, part) {
+                    (BoatDirection::Forward, 1) => horizontal += 1,
+                    (BoatDirection::Forward, _) => {
+                        horizontal += 1;
+                        depth += aim;
+                    }
+                    (BoatDirection::Down, 1) => depth += 1,
+                    (BoatDirection::Up, 1) => depth -= 1,
+                    (BoatDirection::Down, _) => aim += 1,
+                    (BoatDirection::Up, _) => aim -= 1,
+                }
+            }
+        }
+        Answer::from(horizontal * depth)
+    }
+
+    #[test]
+    fn matches_reference() {
+        differential::check::<Day2>(2000, 20, reference)
+    }
+}
@@ -0,0 +1,250 @@
+use core::ops::Range;
+
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::input;
+use crate::input::InputError;
+use crate::rng::Rng;
+
+pub static DATA_FILEPATH: &str = "2021/day3.txt";
+
+pub type Input = Vec<String>;
+
+pub fn parse(raw: &str) -> Result<Input, InputError> {
+    let mut bit_strings: Input = Vec::new();
+    for line in input::lines(raw) {
+        line.check_chars(|c| c == '0' || c == '1', "`0` or `1`")?;
+        if let Some(first) = bit_strings.first() {
+            if line.text.len() > first.len() {
+                return Err(line.error(&line.text[first.len()..], "end of line"));
+            } else if line.text.len() < first.len() {
+                return Err(line.missing(&format!("{} bits like the first line", first.len())));
+            }
+        }
+        bit_strings.push(line.text.to_string());
+    }
+    Ok(bit_strings)
+}
+
+pub fn part1(bit_strings: &Input) -> i64 {
+    let no_bits = bit_strings[0].len();
+    let mut zeros = vec![0; no_bits];
+    let mut ones = vec![0; no_bits];
+
+    for bit_string in bit_strings {
+        for (index, bit) in bit_string.chars().enumerate() {
+            if bit == '0' {
+                zeros[index] += 1;
+            } else {
+                ones[index] += 1;
+            }
+        }
+    }
+
+    let bits_range = Range {
+        start: 0,
+        end: no_bits,
+    };
+
+    let mut gamma_bits: Vec<i64> = vec![0; no_bits];
+    for id in bits_range {
+        if zeros[id] > ones[id] {
+            gamma_bits[id] = 0
+        } else {
+            gamma_bits[id] = 1
+        }
+    }
+
+    let mut gamma_rate = 0;
+    let mut epsilon_rate = 0;
+    for (idx, value) in gamma_bits.iter().rev().enumerate() {
+        if value == &1 {
+            gamma_rate += 1 << idx;
+        } else {
+            epsilon_rate += 1 << idx;
+        }
+    };
+    epsilon_rate * gamma_rate
+}
+
+type TakeOnesWhenFn = fn(a: i64, b: i64) -> bool;
+
+fn find_at(nums: Vec<Vec<char>>, idx: usize, take_ones_when_fn: TakeOnesWhenFn) -> Vec<char> {
+    let mut ones_at_idx = 0;
+    let mut zeroes_at_idx = 0;
+
+    for num in nums.iter() {
+        if num[idx] == '0' {
+            zeroes_at_idx += 1;
+        } else {
+            ones_at_idx += 1;
+        }
+    }
+
+    let more_at_idx = if take_ones_when_fn(zeroes_at_idx, ones_at_idx) {
+        '1'
+    } else {
+        '0'
+    };
+
+    let filtered_nums: Vec<Vec<char>> =
+        nums.into_iter().filter(|n| n[idx] != more_at_idx).collect();
+
+    if filtered_nums.len() == 1 {
+        filtered_nums.first().unwrap().to_owned()
+    } else {
+        find_at(filtered_nums, idx + 1, take_ones_when_fn)
+    }
+}
+
+fn find_less(nums: Vec<Vec<char>>) -> Vec<char> {
+    if nums.len() == 1 {
+        nums.first().unwrap().to_owned()
+    } else {
+        find_at(nums, 0, |zero_count_at_idx, one_count_at_idx| {
+            zero_count_at_idx > one_count_at_idx
+        })
+    }
+}
+
+fn find_more(nums: Vec<Vec<char>>) -> Vec<char> {
+    if nums.len() == 1 {
+        nums.first().unwrap().to_owned()
+    } else {
+        find_at(nums, 0, |zero_count_at_idx, one_count_at_idx| {
+            zero_count_at_idx <= one_count_at_idx
+        })
+    }
+}
+
+fn bitstring_to_i64(bit_string: Vec<char>) -> i64 {
+    let mut result = 0;
+    for (idx, value) in bit_string.iter().rev().enumerate() {
+        if value == &'1' {
+            result += 1 << idx;
+        }
+    }
+    result
+}
+
+pub fn part2(bit_strings: &Input) -> i64 {
+    let nums: Vec<Vec<char>> = bit_strings
+        .iter()
+        .map(|bit_string| bit_string.chars().collect())
+        .collect();
+
+    let more_result = bitstring_to_i64(find_more(nums.to_owned())); //TODO: Can I just borrow read only?
+    let less_result = bitstring_to_i64(find_less(nums));
+    more_result * less_result
+}
+
+/// Rating as the puzzle defines it, keeping the numbers with the most or the
+/// least common bit at each position in turn, `None` if that keeps none.
+fn rating(bit_strings: &[String], most_common: bool) -> Option<i64> {
+    let mut left: Vec<&String> = bit_strings.iter().collect();
+    let mut idx = 0;
+    while left.len() > 1 && idx < left[0].len() {
+        let ones = left.iter().filter(|bits| bits.as_bytes()[idx] == b'1').count();
+        let keep = if most_common == (2 * ones >= left.len()) { b'1' } else { b'0' };
+        left.retain(|bits| bits.as_bytes()[idx] == keep);
+        idx += 1;
+    }
+    match left[..] {
+        [bits] => Some(i64::from_str_radix(bits, 2).unwrap()),
+        _ => None,
+    }
+}
+
+/// Whether both parts have a single answer: no position has as many zeros as
+/// ones and both ratings keep exactly one number.
+fn is_unambiguous(bit_strings: &[String]) -> bool {
+    let columns_differ = (0..bit_strings[0].len()).all(|idx| {
+        let ones = bit_strings.iter().filter(|bits| bits.as_bytes()[idx] == b'1').count();
+        2 * ones != bit_strings.len()
+    });
+    columns_differ && rating(bit_strings, true).is_some() && rating(bit_strings, false).is_some()
+}
+
+pub struct Day3;
+
+impl Day for Day3 {
+    type Input = Input;
+
+    fn parse(raw: &str) -> Result<Input, InputError> {
+        parse(raw)
+    }
+
+    fn part1(input: &Input) -> Answer {
+        Answer::from(part1(input))
+    }
+
+    fn part2(input: &Input) -> Answer {
+        Answer::from(part2(input))
+    }
+
+    /// Up to `4 * size` distinct numbers of up to 12 bits.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        loop {
+            let width = 1 + rng.below(12);
+            let count = 1 + rng.below((4 * size).min(1 << width));
+            let mut numbers: Vec<String> = Vec::new();
+            while numbers.len() < count {
+                let bits: String = (0..width).map(|_| *rng.choose(&['0', '1'])).collect();
+                if !numbers.contains(&bits) {
+                    numbers.push(bits);
+                }
+            }
+            if is_unambiguous(&numbers) {
+                return Some(numbers.iter().map(|bits| format!("{}\n", bits)).collect());
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day::solve_file;
+    use crate::differential;
+    static TEST_DATA_FILEPATH: &str = "2021/day3_test.txt";
+
+    #[test]
+    fn task1_test_data() {
+        assert_eq!(Answer::Int(198), solve_file::<Day3>(TEST_DATA_FILEPATH, 1))
+    }
+
+    #[test]
+    fn task1() {
+        assert_eq!(Answer::Int(741950), solve_file::<Day3>(DATA_FILEPATH, 1))
+    }
+
+    #[test]
+    fn task2_test_data() {
+        assert_eq!(Answer::Int(230), solve_file::<Day3>(TEST_DATA_FILEPATH, 2))
+    }
+
+    #[test]
+    fn task2() {
+        assert_eq!(Answer::Int(903810), solve_file::<Day3>(DATA_FILEPATH, 2))
+    }
+
+    fn reference(bit_strings: &Input, part: u32) -> Answer {
+        if part == 2 {
+            return Answer::from(rating(bit_strings, true).unwrap() * rating(bit_strings, false).unwrap());
+        }
+        let width = bit_strings[0].len();
+        let gamma = (0..width).fold(0, |gamma, idx| {
+            let ones = bit_strings.iter().filter(|bits| bits.as_bytes()[idx] == b'1').count();
+            2 * gamma + i64::from(2 * ones > bit_strings.len())
+        });
+        Answer::from(gamma * ((1 << width) - 1 - gamma))
+    }
+
+    #[test]
+    fn matches_reference() {
+        differential::check::<Day3>(2000, 20, reference)
+    }
+}
+
+
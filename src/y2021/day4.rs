@@ -0,0 +1,405 @@
+use std::ops::Range;
+
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::input;
+use crate::input::InputError;
+use crate::input::Line;
+use crate::rng::Rng;
+
+pub static DATA_FILEPATH: &str = "2021/day4.txt";
+
+static BOARD_SIZE: usize = 5;
+
+pub type Row = Vec<i8>;
+
+pub type DrawnNumbers = Vec<i8>;
+
+#[derive(Debug, Clone)]
+pub struct Board {
+    pub rows: Vec<Row>,
+}
+
+impl Board {
+    fn new(rows: Vec<Row>) -> Self {
+        Board { rows }
+    }
+
+    fn render(&self) -> String {
+        self.rows
+            .iter()
+            .map(|row| row.iter().map(|num| format!("{:>2}", num)).collect::<Vec<_>>().join(" "))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BoardGame {
+    pub boards: Vec<Board>,
+    pub numbers: DrawnNumbers,
+}
+
+pub type Input = BoardGame;
+
+fn read_boards<'a>(raw: &str, mut boards_lines: impl Iterator<Item = Line<'a>>) -> Result<Vec<Board>, InputError> {
+    let mut boards: Vec<Board> = Vec::new();
+    let board_rows = format!("{} rows per board", BOARD_SIZE);
+
+    // The drawn numbers are followed by a blank line.
+    if let Some(line) = boards_lines.next().filter(|line| !line.text.is_empty()) {
+        return Err(line.error(line.text, "a blank line after the drawn numbers"));
+    }
+
+    let mut rows: Vec<Row> = Vec::new();
+    loop {
+        let line = boards_lines.next();
+
+        match line {
+            Some(line) => {
+                if line.text.is_empty() {
+                    if rows.len() < BOARD_SIZE {
+                        return Err(line.error(line.text, &board_rows));
+                    }
+                    boards.push(Board::new(rows.to_owned()));
+                    rows = Vec::new();
+                } else {
+                    if rows.len() == BOARD_SIZE {
+                        return Err(line.error(line.text, "a blank line between boards"));
+                    }
+                    let line_nums: Vec<&str> = line.text.split(' ').filter(|l| l != &"").collect();
+                    if let Some(extra) = line_nums.get(BOARD_SIZE) {
+                        return Err(line.error(extra, "end of line"));
+                    } else if line_nums.len() < BOARD_SIZE {
+                        return Err(line.missing(&format!("{} numbers per row", BOARD_SIZE)));
+                    }
+                    let line_nums: Vec<i8> = line_nums
+                        .iter()
+                        .map(|l| line.parse(l, "a number"))
+                        .collect::<Result<_, _>>()?;
+                    rows.push(line_nums)
+                }
+            }
+            _ => {
+                // A trailing blank line ends the last board early.
+                if !rows.is_empty() && rows.len() < BOARD_SIZE {
+                    return Err(input::end_of_input(raw, &board_rows));
+                } else if !rows.is_empty() {
+                    boards.push(Board::new(rows.to_owned()));
+                }
+                break;
+            }
+        }
+    }
+    if boards.is_empty() {
+        return Err(input::end_of_input(raw, "a board"));
+    }
+    Ok(boards)
+}
+
+fn read_nums(line: Line) -> Result<Vec<i8>, InputError> {
+    line.text.split(',').map(|s| line.parse(s, "a number")).collect()
+}
+
+pub fn parse(raw: &str) -> Result<Input, InputError> {
+    let mut lines = input::lines(raw);
+    let numbers_line = lines
+        .next()
+        .ok_or_else(|| input::end_of_input(raw, "the drawn numbers"))?;
+    let numbers = read_nums(numbers_line)?;
+    let boards = read_boards(raw, lines)?;
+    Ok(BoardGame {
+        boards,
+        numbers,
+    })
+}
+
+fn mark_num(boards: &mut Vec<Board>, num: i8) {
+    for board in boards {
+        board.rows = board
+            .rows
+            .iter()
+            .map(|r| r.iter().map(|c| if c == &num { -1 } else { *c }).collect())
+            .collect();
+    }
+}
+
+fn is_board_winner(board: &Board) -> bool {
+    for row in &board.rows {
+        let mut is_winner = true;
+        for cell in row {
+            if cell != &-1 {
+                is_winner = false;
+            }
+        }
+        if is_winner {
+            return true;
+        }
+    }
+
+    let row_idx_range = Range { start: 0, end: BOARD_SIZE };
+    for row_idx in row_idx_range {
+        let mut is_winner = true;
+        for row in board.rows.iter() {
+            if row[row_idx] != -1 {
+                is_winner = false;
+            }
+        }
+
+        if is_winner {
+            return true;
+        }
+    }
+    false
+}
+
+/// Drops the boards as they win, the last one to go is the true winner. When
+/// the last boards win on the same draw, the last of them in input order is.
+fn find_winning_board_extended(mut boards: Vec<Board>, nums: DrawnNumbers) -> Option<i64> {
+    for num in nums {
+        mark_num(&mut boards, num);
+        if boards.iter().all(is_board_winner) {
+            return boards.last().map(|board| calculate_result(board, num));
+        }
+        boards.retain(|board| !is_board_winner(board));
+    }
+    None
+}
+
+fn play_game_extended(boards: Vec<Board>, nums: DrawnNumbers) -> i64 {
+    find_winning_board_extended(boards, nums).expect("Failed to find winning board")
+}
+
+fn winner(boards: &[Board]) -> Option<&Board> {
+    boards.iter().find(|board| is_board_winner(board))
+}
+
+fn calculate_result(board: &Board, num: i8) -> i64 {
+    let mut result: i64 = 0;
+    for row in &board.rows {
+        for cell in row {
+            if cell != &-1 {
+                result += i64::from(*cell);
+            }
+        }
+    }
+    result * i64::from(num)
+}
+
+fn find_winning_board(mut boards: Vec<Board>, nums: DrawnNumbers) -> Option<i64> {
+    for num in nums {
+        mark_num(&mut boards, num);
+        if let Some(board) = winner(&boards) {
+            return Some(calculate_result(board, num));
+        }
+    }
+    None
+}
+
+fn play_game(boards: Vec<Board>, nums: DrawnNumbers) -> i64 {
+    find_winning_board(boards, nums).expect("Failed to find winning board")
+}
+
+/// Boards are marked while playing, so each part plays on its own copy.
+pub fn part1(board_game: &Input) -> i64 {
+    play_game(board_game.boards.clone(), board_game.numbers.clone())
+}
+
+pub fn part2(board_game: &Input) -> i64 {
+    play_game_extended(board_game.boards.clone(), board_game.numbers.clone())
+}
+
+fn show_board(board_game: &Input, n: &str) -> Result<String, String> {
+    n.parse::<usize>()
+        .ok()
+        .and_then(|n| board_game.boards.get(n.checked_sub(1)?))
+        .map(Board::render)
+        .ok_or_else(|| format!("No board {}, there are {}", n, board_game.boards.len()))
+}
+
+/// The row or column of `board` completed first by `numbers` and the index of
+/// the drawn number completing it, if any.
+fn winning_line(board: &Board, numbers: &[i8]) -> Option<(usize, Vec<i8>)> {
+    let turn = |num: &i8| numbers.iter().position(|drawn| drawn == num);
+    let columns = (0..BOARD_SIZE).map(|col| board.rows.iter().map(|row| row[col]).collect::<Vec<_>>());
+    board
+        .rows
+        .iter()
+        .cloned()
+        .chain(columns)
+        .filter_map(|line| Some((line.iter().map(turn).collect::<Option<Vec<_>>>()?.into_iter().max()?, line)))
+        .min_by_key(|(turn, _)| *turn)
+}
+
+pub struct Day4;
+
+impl Day for Day4 {
+    type Input = Input;
+
+    const COMMANDS: &'static [&'static str] = &[
+        "show board <n>  the n-th board, counting from 1",
+        "show numbers    the drawn numbers",
+    ];
+
+    fn parse(raw: &str) -> Result<Input, InputError> {
+        parse(raw)
+    }
+
+    fn part1(input: &Input) -> Answer {
+        Answer::from(part1(input))
+    }
+
+    fn part2(input: &Input) -> Answer {
+        Answer::from(part2(input))
+    }
+
+    /// Up to `size` boards over the numbers below 25 to 99. Half of the boards
+    /// after the first take over the winning line of an earlier one, so that
+    /// boards often win on the same draw.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut numbers: Vec<i8> = (0..rng.range(25..100) as i8).collect();
+        let mut drawn = numbers.clone();
+        rng.shuffle(&mut drawn);
+        let mut boards: Vec<Board> = Vec::new();
+        for _ in 0..1 + rng.below(size) {
+            let line = if boards.is_empty() || rng.chance(1, 2) {
+                Vec::new()
+            } else {
+                winning_line(rng.choose(&boards), &drawn).unwrap().1
+            };
+            rng.shuffle(&mut numbers);
+            let cells: Vec<i8> = numbers
+                .iter()
+                .filter(|num| !line.contains(num))
+                .take(BOARD_SIZE * BOARD_SIZE - line.len())
+                .copied()
+                .collect();
+            let mut rows: Vec<Row> = cells.chunks(BOARD_SIZE).map(<[i8]>::to_vec).collect();
+            if !line.is_empty() {
+                rows.insert(rng.below(BOARD_SIZE), line);
+            }
+            boards.push(Board::new(rows));
+        }
+        let drawn: Vec<String> = drawn.iter().map(|num| num.to_string()).collect();
+        let boards: Vec<String> = boards.iter().map(Board::render).collect();
+        Some(format!("{}\n\n{}\n", drawn.join(","), boards.join("\n\n")))
+    }
+
+    fn inspect(input: &Input, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        match (command, args) {
+            ("show", ["board", n]) => Some(show_board(input, n)),
+            ("show", ["numbers"]) => {
+                let numbers: Vec<String> = input.numbers.iter().map(|num| num.to_string()).collect();
+                Some(Ok(numbers.join(",")))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day;
+    use crate::day::solve_file;
+    use crate::differential;
+    static TEST_DATA_FILEPATH: &str = "2021/day4_test.txt";
+
+    fn winning_turn(board: &Board, numbers: &[i8]) -> Option<usize> {
+        winning_line(board, numbers).map(|(turn, _)| turn)
+    }
+
+    #[test]
+    fn task1_test_data() {
+        assert_eq!(Answer::Int(4512), solve_file::<Day4>(TEST_DATA_FILEPATH, 1))
+    }
+
+    #[test]
+    fn task1() {
+        assert_eq!(Answer::Int(39984), solve_file::<Day4>(DATA_FILEPATH, 1))
+    }
+
+    #[test]
+    fn task2_test_data() {
+        assert_eq!(Answer::Int(1924), solve_file::<Day4>(TEST_DATA_FILEPATH, 2))
+    }
+
+    #[test]
+    fn task2() {
+        assert_eq!(Answer::Int(8468), solve_file::<Day4>(DATA_FILEPATH, 2))
+    }
+
+    #[test]
+    fn inspect_shows_board() {
+        let input = parse("1,2\n\n22 13 17 11  0\n 8  2 23  4 24\n21  9 14 16  7\n 6 10  3 18  5\n 1 12 20 15 19\n").unwrap();
+        let board = Day4::inspect(&input, "show", &["board", "1"]).unwrap().unwrap();
+        assert_eq!("22 13 17 11  0", board.lines().next().unwrap());
+        assert!(Day4::inspect(&input, "show", &["board", "2"]).unwrap().is_err());
+        assert!(Day4::inspect(&input, "step", &["1"]).is_none());
+    }
+
+    /// Scores the board winning first or last by when each of its lines
+    /// completes. Of boards winning on the same draw, the first one in input
+    /// order wins first and the last one wins last.
+    fn reference(board_game: &Input, part: u32) -> Answer {
+        let turns = board_game.boards.iter().filter_map(|board| Some((winning_turn(board, &board_game.numbers)?, board)));
+        let (turn, board) = if part == 1 { turns.min_by_key(|(turn, _)| *turn) } else { turns.max_by_key(|(turn, _)| *turn) }.unwrap();
+        let drawn = &board_game.numbers[..=turn];
+        let unmarked: i64 = board.rows.iter().flatten().filter(|num| !drawn.contains(num)).map(|num| i64::from(*num)).sum();
+        Answer::from(unmarked * i64::from(board_game.numbers[turn]))
+    }
+
+    #[test]
+    fn matches_reference() {
+        differential::check::<Day4>(500, 10, reference)
+    }
+
+    #[test]
+    fn generates_ties() {
+        let ties = (0..100)
+            .filter(|seed| {
+                let input = parse(&day::generate::<Day4>(*seed, 10).unwrap()).unwrap();
+                let mut turns: Vec<usize> =
+                    input.boards.iter().map(|board| winning_turn(board, &input.numbers).unwrap()).collect();
+                turns.sort();
+                turns.len() > 1 && turns[turns.len() - 1] == turns[turns.len() - 2]
+            })
+            .count();
+        assert!(ties > 10, "only {} ties for the last board", ties);
+    }
+
+    #[test]
+    fn last_boards_win_on_the_same_draw() {
+        let first = " 1  2  3  4  5\n 6  7  8  9 10\n11 12 13 14 15\n16 17 18 19 20\n21 22 23 24 25\n";
+        let second = "26 27 28 29  5\n30 31 32 33 34\n35 36 37 38 39\n40 41 42 43 44\n45 46 47 48 49\n";
+        let input = parse(&format!("1,2,3,4,26,27,28,29,5\n\n{}\n{}", first, second)).unwrap();
+        assert_eq!(Answer::Int(5 * (6..=25).sum::<i64>()), Day4::part1(&input));
+        assert_eq!(Answer::Int(5 * (30..=49).sum::<i64>()), Day4::part2(&input));
+        assert_eq!(reference(&input, 1), Day4::part1(&input));
+        assert_eq!(reference(&input, 2), Day4::part2(&input));
+    }
+
+    #[test]
+    fn parse_reports_misshapen_boards() {
+        let board = " 1  2  3  4  5\n 6  7  8  9 10\n11 12 13 14 15\n16 17 18 19 20\n21 22 23 24 25\n";
+        assert_eq!(1, parse(&format!("1,2\n\n{}\n", board)).unwrap().boards.len());
+        let err = parse(&format!("1,2\n{}", board)).unwrap_err();
+        assert_eq!((2, "a blank line after the drawn numbers"), (err.line, err.expected.as_str()));
+        let err = parse(&format!("1,2\n\n{}{}", board, board)).unwrap_err();
+        assert_eq!((8, "a blank line between boards"), (err.line, err.expected.as_str()));
+        let err = parse(&format!("1,2\n\n{}\n\n{}", board, board)).unwrap_err();
+        assert_eq!((9, "5 rows per board"), (err.line, err.expected.as_str()));
+        let err = parse(&format!("1,2\n\n{}", &board[..60])).unwrap_err();
+        assert_eq!((7, "5 rows per board", "end of input"), (err.line, err.expected.as_str(), err.found.as_str()));
+        assert_eq!("a board", parse("1,2\n").unwrap_err().expected);
+    }
+
+    #[test]
+    fn wins_by_column() {
+        let input = parse("1,2,3,4,5,6\n\n 1  7  8  9 10\n 2 11 12 13 14\n 3 15 16 17 18\n 4 19 20 21 22\n 5 23 24 25 26\n").unwrap();
+        assert_eq!(Answer::Int(5 * (7..=26).sum::<i64>()), Day4::part1(&input));
+    }
+}
+
+
+
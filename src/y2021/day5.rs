@@ -0,0 +1,218 @@
+use std::cmp;
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::input;
+use crate::input::InputError;
+use crate::input::Line;
+use crate::rng::Rng;
+
+pub static DATA_FILEPATH: &str = "2021/day5.txt";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    fn read(line: &Line, point_str: &str) -> Result<Self, InputError> {
+        let (x, y) = point_str
+            .split_once(',')
+            .ok_or_else(|| line.error(point_str, "a point `x,y`"))?;
+        let x: i64 = line.parse(x, "an integer")?;
+        let y: i64 = line.parse(y, "an integer")?;
+        Ok(Point { x, y })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinesRange {
+    pub start: Point,
+    pub end: Point,
+}
+
+pub type Input = Vec<LinesRange>;
+
+pub fn parse(raw: &str) -> Result<Input, InputError> {
+    let mut ranges: Vec<LinesRange> = Vec::new();
+    for line in input::lines(raw) {
+        let (start_point, end_point) = line
+            .text
+            .split_once(" -> ")
+            .ok_or_else(|| line.missing("` -> ` followed by the end point"))?;
+        ranges.push(LinesRange {
+            start: Point::read(&line, start_point)?,
+            end: Point::read(&line, end_point)?,
+        })
+    }
+    Ok(ranges)
+}
+
+fn calculate_overlaps<'a>(ranges: impl Iterator<Item = &'a LinesRange>) -> i64 {
+    let mut counts = HashMap::<(i64, i64), i64>::new();
+    for range in ranges {
+        if range.start.x == range.end.x {
+            let x = range.start.x;
+            let y_start = cmp::min(range.start.y, range.end.y);
+            let y_end = cmp::max(range.start.y, range.end.y);
+            let range = Range {
+                start: y_start,
+                end: y_end + 1,
+            };
+
+            for y in range {
+                let count = counts.entry((x, y)).or_insert(0);
+                *count += 1;
+            }
+        } else if range.start.y == range.end.y {
+            let y = range.start.y;
+            let x_start = cmp::min(range.start.x, range.end.x);
+            let x_end = cmp::max(range.start.x, range.end.x);
+
+            let range = Range {
+                start: x_start,
+                end: x_end + 1,
+            };
+
+            for x in range {
+                let count = counts.entry((x, y)).or_insert(0);
+                *count += 1;
+            }
+        } else {
+            let x_diff = if range.start.x > range.end.x { -1 } else { 1 };
+            let y_diff = if range.start.y > range.end.y { -1 } else { 1 };
+
+            let mut x = range.start.x;
+            let mut y = range.start.y;
+
+            loop {
+                let count = counts.entry((x, y)).or_insert(0);
+                *count += 1;
+
+                if x == range.end.x && y == range.end.y {
+                    break;
+                }
+
+                x += x_diff;
+                y += y_diff;
+            }
+        }
+    }
+    let mut overlaps = 0;
+    for count in counts.values() {
+        if count > &1 {
+            overlaps += 1
+        }
+    }
+    overlaps
+}
+
+pub fn part1(lines_ranges: &Input) -> i64 {
+    let non_diag_lines_ranges = lines_ranges
+        .iter()
+        .filter(|r| r.start.x == r.end.x || r.start.y == r.end.y);
+
+    calculate_overlaps(non_diag_lines_ranges)
+}
+
+pub fn part2(lines_ranges: &Input) -> i64 {
+    calculate_overlaps(lines_ranges.iter())
+}
+
+pub struct Day5;
+
+impl Day for Day5 {
+    type Input = Input;
+
+    fn parse(raw: &str) -> Result<Input, InputError> {
+        parse(raw)
+    }
+
+    fn part1(input: &Input) -> Answer {
+        Answer::from(part1(input))
+    }
+
+    fn part2(input: &Input) -> Answer {
+        Answer::from(part2(input))
+    }
+
+    /// `size` horizontal, vertical or diagonal lines in a square of side
+    /// `2 * size + 10`, crowded enough to overlap.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let side = 2 * size as i64 + 10;
+        let lines: Vec<String> = (0..size)
+            .map(|_| {
+                let (x1, y1) = (rng.range(0..side), rng.range(0..side));
+                let (dx, dy) = *rng.choose(&[(1, 0), (0, 1), (1, 1), (1, -1)]);
+                let len = rng.range(0..side);
+                let (x2, y2) = (x1 + dx * len, y1 + dy * len);
+                match rng.chance(1, 2) {
+                    true => format!("{},{} -> {},{}\n", x1, y1, x2, y2),
+                    false => format!("{},{} -> {},{}\n", x2, y2, x1, y1),
+                }
+            })
+            .collect();
+        Some(lines.concat())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day::solve_file;
+    use crate::differential;
+    static TEST_DATA_FILEPATH: &str = "2021/day5_test.txt";
+
+    #[test]
+    fn task1_test_data() {
+        assert_eq!(Answer::Int(5), solve_file::<Day5>(TEST_DATA_FILEPATH, 1))
+    }
+
+    #[test]
+    fn task1() {
+        assert_eq!(Answer::Int(7473), solve_file::<Day5>(DATA_FILEPATH, 1))
+    }
+
+    #[test]
+    fn task2_test_data() {
+        assert_eq!(Answer::Int(12), solve_file::<Day5>(TEST_DATA_FILEPATH, 2))
+    }
+
+    #[test]
+    fn task2() {
+        assert_eq!(Answer::Int(24164), solve_file::<Day5>(DATA_FILEPATH, 2))
+    }
+
+    /// Checks every point of the bounding box against every line.
+    fn reference(lines_ranges: &Input, part: u32) -> Answer {
+        let lines: Vec<&LinesRange> = lines_ranges
+            .iter()
+            .filter(|r| part == 2 || r.start.x == r.end.x || r.start.y == r.end.y)
+            .collect();
+        let covers = |r: &LinesRange, x: i64, y: i64| {
+            let len = cmp::max((r.end.x - r.start.x).abs(), (r.end.y - r.start.y).abs());
+            (0..=len).any(|t| {
+                r.start.x + t * (r.end.x - r.start.x).signum() == x && r.start.y + t * (r.end.y - r.start.y).signum() == y
+            })
+        };
+        let points = lines_ranges.iter().flat_map(|r| [&r.start, &r.end]);
+        let (min_x, max_x) = (points.clone().map(|p| p.x).min().unwrap(), points.clone().map(|p| p.x).max().unwrap());
+        let (min_y, max_y) = (points.clone().map(|p| p.y).min().unwrap(), points.map(|p| p.y).max().unwrap());
+        let overlaps = (min_x..=max_x)
+            .flat_map(|x| (min_y..=max_y).map(move |y| (x, y)))
+            .filter(|&(x, y)| lines.iter().filter(|r| covers(r, x, y)).count() > 1)
+            .count();
+        Answer::from(overlaps as i64)
+    }
+
+    #[test]
+    fn matches_reference() {
+        differential::check::<Day5>(150, 12, reference)
+    }
+}
+
+
+
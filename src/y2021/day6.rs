@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::input;
+use crate::input::InputError;
+use crate::rng::Rng;
+
+pub static DATA_FILEPATH: &str = "2021/day6.txt";
+
+type Cache = HashMap<(i64, i64), i64>;
+
+pub type Input = Vec<i64>;
+
+pub fn parse(raw: &str) -> Result<Input, InputError> {
+    let mut fishes: Vec<i64> = Vec::new();
+    for line in input::lines(raw) {
+        for number in line.text.split(',') {
+            fishes.push(line.parse(number, "a number")?)
+        }
+    }
+    Ok(fishes)
+}
+
+/// Recursively counts fish children population.
+fn single_fish_after(fish_state: i64, days_left: i64, cache: &mut Cache) -> i64 {
+    let cached_value = cache.get(&(fish_state, days_left));
+    match cached_value {
+        Some(value) => *value,
+        None => {
+            let result = if days_left <= 0 {
+                0
+            }  else if fish_state == 0 {
+                1 + single_fish_after(6, days_left - 1, cache)
+                    + single_fish_after(8, days_left - 1, cache)
+            } else {
+                single_fish_after(0, days_left - fish_state, cache)
+            };
+            cache.insert((fish_state, days_left), result);
+            result
+        }
+    }
+}
+
+pub fn fishes_count_after(state: &[i64], days_left: i64) -> i64 {
+    let mut cache: Cache = Cache::new();
+    let initial_sum = state.len() as i64;
+    let children_sum: i64 = state.iter().map(|f| single_fish_after(*f, days_left, &mut cache)).sum();
+    initial_sum + children_sum
+}
+
+pub fn part1(initial: &Input) -> i64 {
+    fishes_count_after(initial, 80)
+}
+
+pub fn part2(initial: &Input) -> i64 {
+    fishes_count_after(initial, 256)
+}
+
+pub struct Day6;
+
+impl Day for Day6 {
+    type Input = Input;
+
+    fn parse(raw: &str) -> Result<Input, InputError> {
+        parse(raw)
+    }
+
+    fn part1(input: &Input) -> Answer {
+        Answer::from(part1(input))
+    }
+
+    fn part2(input: &Input) -> Answer {
+        Answer::from(part2(input))
+    }
+
+    /// `size` fish with any timer.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let fishes: Vec<String> = (0..size).map(|_| rng.range(0..9).to_string()).collect();
+        Some(format!("{}\n", fishes.join(",")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day::solve_file;
+    use crate::differential;
+    static TEST_DATA_FILEPATH: &str = "2021/day6_test.txt";
+
+    #[test]
+    fn task1_test_data() {
+        assert_eq!(Answer::Int(5934), solve_file::<Day6>(TEST_DATA_FILEPATH, 1))
+    }
+
+    #[test]
+    fn task1() {
+        assert_eq!(Answer::Int(358214), solve_file::<Day6>(DATA_FILEPATH, 1));
+    }
+
+    #[test]
+    fn task2_test_data() {
+        assert_eq!(Answer::Int(26984457539), solve_file::<Day6>(TEST_DATA_FILEPATH, 2))
+    }
+
+    #[test]
+    fn task2() {
+        assert_eq!(Answer::Int(1622533344325), solve_file::<Day6>(DATA_FILEPATH, 2))
+    }
+
+    /// Simulates every fish for 80 days, and the number of fish per timer
+    /// for 256 days.
+    fn reference(initial: &Input, part: u32) -> Answer {
+        if part == 1 {
+            let mut fishes = initial.clone();
+            for _ in 0..80 {
+                let born = fishes.iter().filter(|timer| **timer == 0).count();
+                fishes = fishes.iter().map(|timer| if *timer == 0 { 6 } else { timer - 1 }).collect();
+                fishes.extend(std::iter::repeat_n(8, born));
+            }
+            return Answer::from(fishes.len() as i64);
+        }
+        let mut counts = [0; 9];
+        for timer in initial {
+            counts[*timer as usize] += 1;
+        }
+        for _ in 0..256 {
+            counts.rotate_left(1);
+            counts[6] += counts[8];
+        }
+        Answer::from(counts.iter().sum::<i64>())
+    }
+
+    #[test]
+    fn matches_reference() {
+        differential::check::<Day6>(200, 10, reference)
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::day::Variant;
+use crate::input;
+use crate::input::InputError;
+use crate::rng::Rng;
+
+pub static DATA_FILEPATH: &str = "2021/day7.txt";
+
+type FuelCalcFn = fn(crabs: &[i64], target_position: i64) -> i64;
+
+pub type Input = Vec<i64>;
+
+pub fn parse(raw: &str) -> Result<Input, InputError> {
+    let mut crabs: Vec<i64> = Vec::new();
+    for line in input::lines(raw) {
+        for number in line.text.split(',') {
+            crabs.push(line.parse(number, "a number")?)
+        }
+    }
+    Ok(crabs)
+}
+
+fn find_most_common(crabs: &[i64]) -> i64 {
+    let mut counts = HashMap::new();
+    for crab in crabs {
+        *counts.entry(crab).or_insert(0) += 1;
+    }
+    let mut most_common = (crabs.first().unwrap(), 0);
+    for (k, v) in counts {
+        if v > most_common.1 {
+            most_common = (k, v)
+        }
+    }
+    most_common.1
+}
+
+fn calc_fuel_needed(crabs: &[i64], target_position: i64) -> i64 {
+    crabs.iter().map(|c| (target_position - c).abs()).sum()
+}
+
+fn calc_fuel_needed_more_expensive(crabs: &[i64], target_position: i64) -> i64 {
+    crabs
+        .iter()
+        .map(|c| {
+            let dist = (target_position - c).abs();
+            let range = 1..(dist + 1);
+            let sum: i64 = range.sum();
+            sum
+        })
+        .sum()
+}
+
+fn find_best_position(
+    crabs: &[i64],
+    start_position: i64,
+    fuel_needed: i64,
+    fuel_calc: FuelCalcFn,
+) -> i64 {
+    let fuel_needed_one_up = fuel_calc(crabs, start_position + 1);
+    let fuel_needed_one_down = fuel_calc(crabs, start_position - 1);
+
+    if fuel_needed_one_down < fuel_needed {
+        find_best_position(crabs, start_position - 1, fuel_needed_one_down, fuel_calc)
+    } else if fuel_needed_one_up < fuel_needed {
+        find_best_position(crabs, start_position + 1, fuel_needed_one_up, fuel_calc)
+    } else {
+        fuel_needed
+    }
+}
+
+fn find_least_fuel_to_align(crabs: &[i64], fuel_calc: FuelCalcFn) -> i64 {
+    let start_position = find_most_common(crabs);
+    let fuel_needed = fuel_calc(crabs, start_position);
+    find_best_position(crabs, start_position, fuel_needed, fuel_calc)
+}
+
+/// The median minimizes the summed distances.
+fn find_least_fuel_median(crabs: &[i64]) -> i64 {
+    let mut sorted = crabs.to_vec();
+    sorted.sort_unstable();
+    calc_fuel_needed(crabs, sorted[sorted.len() / 2])
+}
+
+/// Fuel for moving `dist` steps when every step costs one more than the last.
+fn triangle(dist: i64) -> i64 {
+    dist * (dist + 1) / 2
+}
+
+/// The best position is within half a step of the mean, so it is either the
+/// mean rounded down or rounded up.
+fn find_least_fuel_mean(crabs: &[i64]) -> i64 {
+    let mean = crabs.iter().sum::<i64>().div_euclid(crabs.len() as i64);
+    [mean, mean + 1]
+        .iter()
+        .map(|target| crabs.iter().map(|c| triangle((target - c).abs())).sum())
+        .min()
+        .unwrap()
+}
+
+pub fn part1(crabs: &Input) -> i64 {
+    find_least_fuel_to_align(crabs, calc_fuel_needed)
+}
+
+pub fn part2(crabs: &Input) -> i64 {
+    find_least_fuel_to_align(crabs, calc_fuel_needed_more_expensive)
+}
+
+pub struct Day7;
+
+impl Day for Day7 {
+    type Input = Input;
+
+    fn parse(raw: &str) -> Result<Input, InputError> {
+        parse(raw)
+    }
+
+    fn part1(input: &Input) -> Answer {
+        Answer::from(part1(input))
+    }
+
+    fn part2(input: &Input) -> Answer {
+        Answer::from(part2(input))
+    }
+
+    fn variants() -> Vec<Variant<Input>> {
+        vec![
+            Variant {
+                part: 1,
+                name: "median",
+                solve: |crabs| Answer::from(find_least_fuel_median(crabs)),
+            },
+            Variant {
+                part: 2,
+                name: "mean",
+                solve: |crabs| Answer::from(find_least_fuel_mean(crabs)),
+            },
+        ]
+    }
+
+    /// `size` crabs at positions below `10 * size`.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let crabs: Vec<String> = (0..size).map(|_| rng.range(0..10 * size as i64).to_string()).collect();
+        Some(format!("{}\n", crabs.join(",")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day::solve_file;
+    use crate::differential;
+    static TEST_DATA_FILEPATH: &str = "2021/day7_test.txt";
+
+    #[test]
+    fn task1_test_data() {
+        assert_eq!(Answer::Int(37), solve_file::<Day7>(TEST_DATA_FILEPATH, 1))
+    }
+
+    #[test]
+    fn task1() {
+        assert_eq!(Answer::Int(352331), solve_file::<Day7>(DATA_FILEPATH, 1));
+    }
+
+    #[test]
+    fn task2_test_data() {
+        assert_eq!(Answer::Int(168), solve_file::<Day7>(TEST_DATA_FILEPATH, 2))
+    }
+
+    // TODO: Introduce cache for distance calculating.
+    #[test] #[ignore]
+    fn task2() {
+        assert_eq!(Answer::Int(99266250), solve_file::<Day7>(DATA_FILEPATH, 2))
+    }
+
+    #[test]
+    fn closed_forms() {
+        let crabs = parse("16,1,2,0,4,2,7,1,2,14\n").unwrap();
+        assert_eq!(37, find_least_fuel_median(&crabs));
+        assert_eq!(168, find_least_fuel_mean(&crabs));
+    }
+
+    #[test]
+    fn calc_fuel_more_expensive() {
+        assert_eq!(calc_fuel_needed_more_expensive(&Vec::from([1]), 5), 10)
+    }
+
+    /// Tries every position between the outermost crabs.
+    fn reference(crabs: &Input, part: u32) -> Answer {
+        let cost = |dist: i64| if part == 1 { dist } else { (1..=dist).sum() };
+        let positions = *crabs.iter().min().unwrap()..=*crabs.iter().max().unwrap();
+        let fuel = positions.map(|target| crabs.iter().map(|c| cost((target - c).abs())).sum::<i64>());
+        Answer::from(fuel.min().unwrap())
+    }
+
+    #[test]
+    fn matches_reference() {
+        differential::check::<Day7>(1000, 15, reference)
+    }
+}
@@ -0,0 +1,470 @@
+use std::collections::HashMap;
+
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::input;
+use crate::input::InputError;
+use crate::input::Line;
+use crate::rng::Rng;
+
+pub static DATA_FILEPATH: &str = "2021/day8.txt";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotesEntry {
+    pub segments: Vec<String>,
+    pub output: Vec<String>,
+}
+
+pub type Input = Vec<NotesEntry>;
+
+/// Segments of each digit on an unscrambled display.
+static DIGIT_SEGMENTS: [&str; 10] = ["abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg"];
+
+/// Segment counts of the ten digits, sorted.
+static DIGIT_LENGTHS: [usize; 10] = [2, 3, 4, 5, 5, 5, 6, 6, 6, 7];
+
+fn read_patterns(line: &Line, raw: &str, count: usize) -> Result<Vec<String>, InputError> {
+    let patterns: Vec<&str> = raw.split(' ').collect();
+    for pattern in &patterns {
+        if pattern.is_empty() || !pattern.chars().all(|c| ('a'..='g').contains(&c)) {
+            return Err(line.error(pattern, "a pattern of segments `a` to `g`"));
+        }
+    }
+    if patterns.len() != count {
+        return Err(line.error(raw, &format!("{} patterns", count)));
+    }
+    Ok(patterns.iter().map(|p| p.to_string()).collect())
+}
+
+fn sorted_segments(pattern: &str) -> Vec<char> {
+    let mut segments: Vec<char> = pattern.chars().collect();
+    segments.sort();
+    segments
+}
+
+pub fn parse(raw: &str) -> Result<Input, InputError> {
+    let mut entries = Vec::<NotesEntry>::new();
+    for line in input::lines(raw) {
+        let (raw_segments, raw_output) = line
+            .text
+            .split_once(" | ")
+            .ok_or_else(|| line.missing("` | ` followed by the output"))?;
+
+        let segments = read_patterns(&line, raw_segments, 10)?;
+        let mut lengths: Vec<usize> = segments.iter().map(|s| s.len()).collect();
+        lengths.sort();
+        if lengths != DIGIT_LENGTHS {
+            return Err(line.error(raw_segments, "one pattern for each of the ten digits"));
+        }
+
+        let output = read_patterns(&line, raw_output, 4)?;
+        for (digit, raw_digit) in output.iter().zip(raw_output.split(' ')) {
+            if !segments.iter().any(|s| sorted_segments(s) == sorted_segments(digit)) {
+                return Err(line.error(raw_digit, "one of the patterns before ` | `"));
+            }
+        }
+        entries.push(NotesEntry { segments, output })
+    }
+    Ok(entries)
+}
+
+pub fn part1(entries: &Input) -> i64 {
+    let mut counts = HashMap::<i8, i64>::new();
+    for entry in entries {
+        for element in &entry.output {
+            match element.len() {
+                2 => *counts.entry(1).or_insert(0) += 1,
+                4 => *counts.entry(4).or_insert(0) += 1,
+                3 => *counts.entry(7).or_insert(0) += 1,
+                7 => *counts.entry(8).or_insert(0) += 1,
+                _ => (),
+            }
+        }
+    }
+    counts.values().sum::<i64>()
+}
+
+type NumsToOptions = HashMap<String, Vec<String>>;
+type CharToResolvedChar = HashMap<String, String>;
+
+fn substract(a: &str, b: &str) -> String {
+    let mut result = String::new();
+    for c in a.chars() {
+        if !b.contains(c) {
+            result.push(c);
+        }
+    }
+    result
+}
+
+fn filter_out_char(nums: NumsToOptions, char_to_filter: String) -> NumsToOptions {
+let mut nums_less_char = NumsToOptions::new();
+    for (k, v) in nums {
+        let v_less_a: Vec<String> = v
+            .iter()
+            .map(|w| {
+                let chars_less_a = w.chars().filter(|c| c.to_string() != char_to_filter).collect();
+                chars_less_a
+            })
+            .collect();
+        nums_less_char.insert(k, v_less_a);
+    };
+    nums_less_char
+}
+
+fn find_char_mapped_to_a(nums: NumsToOptions) -> (NumsToOptions, CharToResolvedChar) {
+    let one = nums.get("1").unwrap().first().unwrap();
+    let seven = nums.get("7").unwrap().first().unwrap();
+    let char_mapped_to_a = substract(seven, one);
+    let mut char_to_resolved = CharToResolvedChar::new();
+    char_to_resolved.insert(char_mapped_to_a.to_string(), "a".to_string());
+    let nums_less_char_mapped_to_a = filter_out_char(nums, char_mapped_to_a);
+    (nums_less_char_mapped_to_a, char_to_resolved)
+}
+
+fn find_char_mapped_to_g_and_nine(
+    mut nums: NumsToOptions,
+    mut resolved: CharToResolvedChar,
+) -> (NumsToOptions, CharToResolvedChar) {
+    let four = nums.get("4").unwrap().first().unwrap();
+    let seven = nums.get("7").unwrap().first().unwrap();
+    let possible_nines = nums.get("069").expect("Expect 069 to be in nums.");
+
+    let mut nine = Vec::<String>::new();
+    let mut others = Vec::<String>::new();
+
+    let mut maybe_char_mapped_to_g: Option<String> = None;
+
+    for possible in possible_nines {
+        let result = substract(&substract(possible, four), seven);
+        if result.len() == 1 {
+            nine.push(possible.to_string());
+            maybe_char_mapped_to_g = Some(result);
+        } else {
+            others.push(possible.to_string());
+        }
+    }
+    let char_mapped_to_g = maybe_char_mapped_to_g.expect("Failed to find char mapped to g");
+    resolved.insert(char_mapped_to_g.to_string(), "g".to_string());
+    nums.insert("06".to_string(), others);
+    nums.insert("9".to_string(), nine);
+    nums.remove("069");
+
+    let nums = filter_out_char(nums, char_mapped_to_g);
+    (nums, resolved)
+}
+
+fn find_char_mapped_to_by_substraction(
+    nums: NumsToOptions,
+    mut resolved: CharToResolvedChar,
+    searched_char: &str,
+    minuend: &str,
+    subtrahend: &str,
+) -> (NumsToOptions, CharToResolvedChar) {
+    let minued = nums.get(minuend).unwrap().first().unwrap();
+    let subtrahend = nums.get(subtrahend).unwrap().first().unwrap();
+
+    let result = substract(minued, subtrahend);
+    if result.len() != 1 {
+        panic!("Failed to find {}", searched_char)
+    };
+
+    let char_mapped_to = result;
+
+    resolved.insert(char_mapped_to.to_string(), searched_char.to_string());
+    let nums = filter_out_char(nums, char_mapped_to);
+    (nums, resolved)
+
+}
+
+fn find_two(
+    mut nums: NumsToOptions,
+    resolved: CharToResolvedChar,
+) -> (NumsToOptions, CharToResolvedChar) {
+    let possible_two = nums.get("235").unwrap();
+
+    let mut two = Vec::<String>::new();
+    let mut others = Vec::<String>::new();
+
+    for possible in possible_two {
+        if possible.len() == 2 {
+            two.push(possible.to_string());
+        } else {
+            others.push(possible.to_string())
+        }
+    }
+
+    if two.len() != 1{
+        panic!("Failed to exctract 2 from 235")
+    }
+
+
+    nums.insert("2".to_string(), two);
+    nums.insert("35".to_string(), others);
+    nums.remove("235");
+
+    (nums, resolved)
+}
+
+fn find_char_mapped_to_c(
+    nums: NumsToOptions,
+    mut resolved: CharToResolvedChar,
+)-> (NumsToOptions, CharToResolvedChar) {
+    let two = nums.get("2").unwrap().first().unwrap();
+    let char_mapped_to_c = two.to_string();
+
+    resolved.insert(char_mapped_to_c.to_string(), "c".to_string());
+    let nums = filter_out_char(nums, char_mapped_to_c.to_string());
+
+    (nums, resolved)
+}
+
+
+fn find_char_mapped_to_f(
+    nums: NumsToOptions,
+    mut resolved: CharToResolvedChar,
+)-> (NumsToOptions, CharToResolvedChar) {
+    let one = nums.get("1").unwrap().first().unwrap();
+    let char_mapped_to_f = one.to_string();
+
+    resolved.insert(char_mapped_to_f.to_string(), "f".to_string());
+    let nums = filter_out_char(nums, char_mapped_to_f.to_string());
+
+    (nums, resolved)
+}
+
+
+/// Groups the patterns of `entry` by the digits their length allows, e.g.
+/// `069` for the patterns with six segments.
+fn group_by_length(entry: &NotesEntry) -> NumsToOptions {
+    let mut nums = HashMap::<String, Vec<String>>::new();
+    for element in entry.segments.iter().cloned() {
+        match element.len() {
+            2 => {
+                nums.insert("1".to_string(), Vec::from([element]));
+            }
+            4 => {
+                nums.insert("4".to_string(), Vec::from([element]));
+            }
+            3 => {
+                nums.insert("7".to_string(), Vec::from([element]));
+            }
+            7 => {
+                nums.insert("8".to_string(), Vec::from([element]));
+            }
+            6 => {
+                if !nums.contains_key("069") {
+                    nums.insert("069".to_string(), Vec::new());
+                }
+                nums.get_mut("069").unwrap().push(element)
+            }
+            5 => {
+                if !nums.contains_key("235") {
+                    nums.insert("235".to_string(), Vec::new());
+                }
+                nums.get_mut("235").unwrap().push(element)
+            }
+            len => panic!("Unexpected element.len() = {}", len),
+        }
+    }
+    nums
+}
+
+/// Scrambled segment to the segment it drives.
+fn resolve_wiring(nums: NumsToOptions) -> CharToResolvedChar {
+    let (nums, chars_to_resolved_chars) = find_char_mapped_to_a(nums);
+    let (nums, chars_to_resolved_chars) = find_char_mapped_to_g_and_nine(nums, chars_to_resolved_chars);
+    let (nums, chars_to_resolved_chars) = find_char_mapped_to_by_substraction(nums, chars_to_resolved_chars, "e", "8", "4");
+    let (nums, chars_to_resolved_chars) = find_two(nums, chars_to_resolved_chars);
+    let (nums, chars_to_resolved_chars) = find_char_mapped_to_by_substraction(nums, chars_to_resolved_chars, "d", "2", "1");
+    let (nums, chars_to_resolved_chars) = find_char_mapped_to_by_substraction(nums, chars_to_resolved_chars, "b", "4", "1");
+    let (nums, chars_to_resolved_chars) = find_char_mapped_to_c(nums, chars_to_resolved_chars);
+    let (_, chars_to_resolved_chars) = find_char_mapped_to_f(nums, chars_to_resolved_chars);
+    chars_to_resolved_chars
+}
+
+/// Resolves the scrambled segment wiring of `entry` and decodes its output.
+pub fn decode_and_figure_value(entry: &NotesEntry) -> i64 {
+    let chars_to_resolved_chars = resolve_wiring(group_by_length(entry));
+
+    let mut digits = Vec::<&str>::new();
+
+    for digit in &entry.output {
+        let mut digit: Vec<String> = digit.chars().map(|c| chars_to_resolved_chars.get(&c.to_string()).unwrap().to_string() ).collect();
+        digit.sort();
+        let digit = digit.join("");
+        digits.push(match digit.as_ref() {
+           "abcefg" => "0",
+           "cf" => "1",
+           "acdeg" => "2",
+           "acdfg" => "3",
+           "bcdf" => "4",
+           "abdfg" => "5",
+           "abdefg" => "6",
+           "acf" => "7",
+           "abcdefg" => "8",
+           "abcdfg" => "9",
+            _ => panic!("Unexpected number encoding"),
+        })
+    }
+
+    let parsed_digits: i64 = digits.join("").parse().unwrap();
+
+    parsed_digits
+}
+
+pub fn part2(entries: &Input) -> i64 {
+    entries
+        .iter()
+        .map(decode_and_figure_value)
+        .sum()
+}
+
+fn show_entry(entries: &Input, n: &str) -> Result<String, String> {
+    let entry = n
+        .parse::<usize>()
+        .ok()
+        .and_then(|n| entries.get(n.checked_sub(1)?))
+        .ok_or_else(|| format!("No entry {}, there are {}", n, entries.len()))?;
+    let nums = group_by_length(entry);
+    let mut lines: Vec<String> = Vec::new();
+    let mut options: Vec<_> = nums.iter().collect();
+    options.sort();
+    for (digits, patterns) in options {
+        lines.push(format!("{}: {}", digits, patterns.join(" ")));
+    }
+    let mut wiring: Vec<_> = resolve_wiring(nums).into_iter().collect();
+    wiring.sort();
+    let wiring: Vec<String> = wiring.iter().map(|(from, to)| format!("{}->{}", from, to)).collect();
+    lines.push(format!("wiring: {}", wiring.join(" ")));
+    lines.push(format!("output: {}", decode_and_figure_value(entry)));
+    Ok(lines.join("\n"))
+}
+
+pub struct Day8;
+
+impl Day for Day8 {
+    type Input = Input;
+
+    const COMMANDS: &'static [&'static str] = &["entry <n>       digit options and wiring of the n-th entry"];
+
+    fn parse(raw: &str) -> Result<Input, InputError> {
+        parse(raw)
+    }
+
+    fn part1(input: &Input) -> Answer {
+        Answer::from(part1(input))
+    }
+
+    fn part2(input: &Input) -> Answer {
+        Answer::from(part2(input))
+    }
+
+    /// `size` entries, each with its own wiring and random output digits.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut entries = String::new();
+        for _ in 0..size {
+            let mut wiring: Vec<char> = ('a'..='g').collect();
+            rng.shuffle(&mut wiring);
+            let scramble = |rng: &mut Rng, digit: usize| {
+                let mut pattern: Vec<char> = DIGIT_SEGMENTS[digit].bytes().map(|segment| wiring[(segment - b'a') as usize]).collect();
+                rng.shuffle(&mut pattern);
+                pattern.into_iter().collect::<String>()
+            };
+            let mut patterns: Vec<String> = (0..10).map(|digit| scramble(rng, digit)).collect();
+            let output: Vec<String> = (0..4)
+                .map(|_| {
+                    let digit = rng.below(10);
+                    scramble(rng, digit)
+                })
+                .collect();
+            rng.shuffle(&mut patterns);
+            entries.push_str(&format!("{} | {}\n", patterns.join(" "), output.join(" ")));
+        }
+        Some(entries)
+    }
+
+    fn inspect(input: &Input, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        match (command, args) {
+            ("entry", [n]) => Some(show_entry(input, n)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day::solve_file;
+    use crate::differential;
+    static TEST_DATA_FILEPATH: &str = "2021/day8_test.txt";
+
+    #[test]
+    fn task1_test_data() {
+        assert_eq!(Answer::Int(26), solve_file::<Day8>(TEST_DATA_FILEPATH, 1))
+    }
+
+    #[test]
+    fn task1() {
+        assert_eq!(Answer::Int(495), solve_file::<Day8>(DATA_FILEPATH, 1));
+    }
+
+    #[test]
+    fn task2_test_data() {
+        assert_eq!(Answer::Int(61229), solve_file::<Day8>(TEST_DATA_FILEPATH, 2))
+    }
+
+    #[test] 
+    fn task2() {
+        assert_eq!(Answer::Int(1055164), solve_file::<Day8>(DATA_FILEPATH, 2))
+    }
+
+    #[test]
+    fn inspect_entry() {
+        let input = parse("acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf\n").unwrap();
+        let shown = Day8::inspect(&input, "entry", &["1"]).unwrap().unwrap();
+        let lines: Vec<&str> = shown.lines().collect();
+        assert_eq!("1: ab", lines[1]);
+        assert_eq!(Some(&"wiring: a->c b->f c->g d->a e->b f->d g->e"), lines.iter().rev().nth(1));
+        assert_eq!(Some(&"output: 5353"), lines.last());
+        assert!(Day8::inspect(&input, "entry", &["2"]).unwrap().is_err());
+    }
+
+    // #[test]
+    // fn calc_fuel_more_expensive() {
+    //     assert_eq!(calc_fuel_needed_more_expensive(&Vec::from([1]), 5), 10)
+    // }
+
+    /// Identifies each digit by how often its segments occur among the ten
+    /// patterns, which differs for every digit whatever the wiring.
+    fn reference(entries: &Input, part: u32) -> Answer {
+        let score = |patterns: &[&str], digit: &str| -> usize {
+            digit.chars().map(|segment| patterns.iter().filter(|pattern| pattern.contains(segment)).count()).sum()
+        };
+        let digits = |entry: &NotesEntry| -> Vec<usize> {
+            let patterns: Vec<&str> = entry.segments.iter().map(String::as_str).collect();
+            entry
+                .output
+                .iter()
+                .map(|digit| (0..10).find(|known| score(&DIGIT_SEGMENTS, DIGIT_SEGMENTS[*known]) == score(&patterns, digit)).unwrap())
+                .collect()
+        };
+        let total: usize = match part {
+            1 => entries.iter().flat_map(digits).filter(|digit| [1, 4, 7, 8].contains(digit)).count(),
+            _ => entries.iter().map(|entry| digits(entry).iter().fold(0, |value, digit| 10 * value + digit)).sum(),
+        };
+        Answer::from(total as i64)
+    }
+
+    #[test]
+    fn matches_reference() {
+        differential::check::<Day8>(300, 10, reference)
+    }
+
+    #[test]
+    fn parse_reports_unknown_digit() {
+        let line = "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gh";
+        let err = parse(line).unwrap_err();
+        assert_eq!((1, 83, "`gh`"), (err.line, err.column, err.found.as_str()));
+    }
+}
@@ -0,0 +1,310 @@
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::debug;
+use crate::input;
+use crate::input::InputError;
+use crate::rng::Rng;
+
+pub static DATA_FILEPATH: &str = "2021/day9.txt";
+
+pub type Row = Vec<i8>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeightMap {
+    pub rows: Vec<Row>,
+}
+
+pub type Input = HeightMap;
+
+impl HeightMap {
+    fn get_point_value(&self, coords: (i8, i8)) -> Option<&i8> {
+        let (row_idx, col_idx) = coords;
+        match self.rows.get(row_idx as usize) {
+            Some(row) => row.get(col_idx as usize),
+            _ => None
+        }
+    }
+}
+
+pub fn parse(raw: &str) -> Result<Input, InputError> {
+    let mut rows: Vec<Row> = Vec::new();
+    for line in input::lines(raw) {
+        debug!("line={:?}", line.text.split("").collect::<Vec<_>>());
+        let row: Row = line.digits()?.into_iter().map(|d| d as i8).collect();
+        rows.push(row)
+    }
+    Ok(HeightMap { rows })
+}
+
+fn get_low_points(map: &HeightMap) -> Vec<(u8, u8)> {
+    let mut low_points: Vec<(u8, u8)> = Vec::new();
+    for row_idx in 0..map.rows.len() {
+        for col_idx in 0..map.rows.first().unwrap().len() {
+            let value = map
+                .rows
+                .get(row_idx)
+                .unwrap_or_else(|| panic!("Expected to find a row with id row_idx: {}", row_idx))
+                .get(col_idx)
+                .unwrap_or_else(|| panic!("Expected to find a col with id col_idx: {}", col_idx));
+            let mut neighbour_values: Vec<i8> = Vec::new();
+
+            if col_idx > 0 {
+                let value_before = map.rows.get(row_idx).unwrap().get(col_idx - 1);
+                if let Some(value) = value_before {
+                    neighbour_values.push(*value)
+                }
+            }
+
+            let value_after = map.rows.get(row_idx).unwrap().get(col_idx + 1);
+            if let Some(value) = value_after {
+                neighbour_values.push(*value)
+            }
+
+            if row_idx > 0 {
+                let row_above = map.rows.get(row_idx - 1);
+                if let Some(row) = row_above {
+                    neighbour_values.push(*row.get(col_idx).unwrap())
+                }
+            }
+
+            let row_below = map.rows.get(row_idx + 1);
+            if let Some(row) = row_below {
+                neighbour_values.push(*row.get(col_idx).unwrap())
+            }
+
+            let is_low_point = neighbour_values.iter().all(|neighbour| neighbour > value);
+            if is_low_point {
+                low_points.push((row_idx.try_into().unwrap(), col_idx.try_into().unwrap()));
+            }
+        }
+    }
+    low_points
+}
+
+
+
+fn get_low_points_values(map: &HeightMap) -> Vec<i8> {
+    get_low_points(map).into_iter().map(|(row_idx, col_idx)| {
+        let value = map.rows.get(row_idx as usize).unwrap().get(col_idx as usize).unwrap();
+        *value
+    }).collect()
+}
+
+fn get_size_of_basin_internal(starting_point: (i8, i8), map: &HeightMap, visited: &mut HashSet::<(i8, i8)>) -> i64 {
+
+    // up
+    // right
+    // bottom
+    // left
+
+    if visited.contains(&starting_point) {
+        return 0
+    }
+
+    match map.get_point_value(starting_point) {
+        Some(point_value) => {
+            visited.insert(starting_point);
+
+            if *point_value == 9 {
+                return 0
+            }
+
+            let mut value: i64 = 1;
+
+            let (x, y) = starting_point;
+            value += get_size_of_basin_internal((x, y + 1), map, visited);
+            value += get_size_of_basin_internal((x + 1, y), map, visited);
+            value += get_size_of_basin_internal((x, y - 1), map, visited);
+            value += get_size_of_basin_internal((x - 1, y), map, visited);
+            value
+        }
+        None => 0,
+    }
+}
+
+fn get_size_of_basin(basin_start: (i8, i8), map: &HeightMap) -> i64 {
+    let mut visited = HashSet::<(i8, i8)>::new();
+    get_size_of_basin_internal(basin_start, map, &mut visited)
+}
+
+pub fn part1(map: &Input) -> i64 {
+    let low_points = get_low_points_values(map);
+    low_points.into_iter().map(|p| i64::from(p) + 1).sum::<i64>()
+}
+
+pub fn part2(map: &Input) -> i64 {
+    let low_points = get_low_points(map);
+    let mut low_points_sizes: Vec<i64> = low_points.into_iter().map(|p| {
+        let (x, y) = p;
+        get_size_of_basin((x as i8, y as i8), map)
+    }).collect();
+    low_points_sizes.sort();
+    low_points_sizes.reverse();
+    let top_three_sizes = &low_points_sizes[..=2];
+    top_three_sizes.iter().product::<i64>()
+}
+
+static NEIGHBOURS: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// Breadth-first distances from `starts` over the cells `passable` allows,
+/// with the start each cell was reached from.
+fn spread(
+    width: usize,
+    height: usize,
+    starts: &[(usize, usize)],
+    passable: impl Fn(usize, (usize, usize)) -> bool,
+) -> Vec<Vec<Option<(usize, usize)>>> {
+    let mut reached: Vec<Vec<Option<(usize, usize)>>> = vec![vec![None; width]; height];
+    let mut queue: VecDeque<(usize, usize)> = VecDeque::new();
+    for (idx, &(row, col)) in starts.iter().enumerate() {
+        reached[row][col] = Some((idx, 0));
+        queue.push_back((row, col));
+    }
+    while let Some((row, col)) = queue.pop_front() {
+        let (start, dist) = reached[row][col].unwrap();
+        for (dr, dc) in NEIGHBOURS {
+            let (Some(r), Some(c)) = (row.checked_add_signed(dr), col.checked_add_signed(dc)) else {
+                continue;
+            };
+            if r < height && c < width && reached[r][c].is_none() && passable(start, (r, c)) {
+                reached[r][c] = Some((start, dist + 1));
+                queue.push_back((r, c));
+            }
+        }
+    }
+    reached
+}
+
+pub struct Day9;
+
+impl Day for Day9 {
+    type Input = Input;
+
+    fn parse(raw: &str) -> Result<Input, InputError> {
+        parse(raw)
+    }
+
+    fn part1(input: &Input) -> Answer {
+        Answer::from(part1(input))
+    }
+
+    fn part2(input: &Input) -> Answer {
+        Answer::from(part2(input))
+    }
+
+    /// A map of up to `size + 3` cells a side, split into at least three
+    /// basins that slope up from their low point and are walled off by 9s.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        loop {
+            let (width, height) = (3 + rng.below(size + 1), 3 + rng.below(size + 1));
+            let seeds: Vec<(usize, usize)> = (0..3 + rng.below(size)).map(|_| (rng.below(height), rng.below(width))).collect();
+            let regions = spread(width, height, &seeds, |_, _| true);
+            // Of two touching regions, the later one gives up the cell.
+            let region = |row: usize, col: usize| regions[row][col].map(|(seed, _)| seed);
+            let is_open = |seed: usize, (row, col): (usize, usize)| {
+                region(row, col) == Some(seed)
+                    && NEIGHBOURS.iter().all(|(dr, dc)| {
+                        match (row.checked_add_signed(*dr), col.checked_add_signed(*dc)) {
+                            (Some(r), Some(c)) if r < height && c < width => region(r, c).unwrap() >= seed,
+                            _ => true,
+                        }
+                    })
+            };
+            let basins: Vec<(usize, usize)> = seeds
+                .iter()
+                .enumerate()
+                .filter(|(seed, cell)| is_open(*seed, **cell) && seeds.iter().position(|other| other == *cell) == Some(*seed))
+                .map(|(_, cell)| *cell)
+                .collect();
+            if basins.len() < 3 {
+                continue;
+            }
+            let bases: Vec<usize> = basins.iter().map(|_| rng.below(4)).collect();
+            let open_seeds: Vec<usize> = basins.iter().map(|cell| region(cell.0, cell.1).unwrap()).collect();
+            let distances = spread(width, height, &basins, |basin, cell| is_open(open_seeds[basin], cell));
+            let rows: Vec<String> = distances
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|reached| match reached {
+                            Some((basin, dist)) if bases[*basin] + dist < 9 => char::from(b'0' + (bases[*basin] + dist) as u8),
+                            _ => '9',
+                        })
+                        .collect()
+                })
+                .collect();
+            return Some(rows.iter().map(|row| format!("{}\n", row)).collect());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day::solve_file;
+    use crate::differential;
+    static TEST_DATA_FILEPATH: &str = "2021/day9_test.txt";
+
+    #[test]
+    fn task1_test_data() {
+        assert_eq!(Answer::Int(15), solve_file::<Day9>(TEST_DATA_FILEPATH, 1))
+    }
+
+    #[test]
+    fn task1() {
+        assert_eq!(Answer::Int(423), solve_file::<Day9>(DATA_FILEPATH, 1));
+    }
+
+    #[test]
+    fn task2_test_data() {
+        assert_eq!(Answer::Int(1134), solve_file::<Day9>(TEST_DATA_FILEPATH, 2))
+    }
+
+    #[test]
+    fn task2() {
+        assert_eq!(Answer::Int(1198704), solve_file::<Day9>(DATA_FILEPATH, 2))
+    }
+
+    /// Low points by comparing with every neighbour, basins as the connected
+    /// areas without 9s.
+    fn reference(map: &Input, part: u32) -> Answer {
+        let (height, width) = (map.rows.len(), map.rows[0].len());
+        let cells = (0..height).flat_map(|row| (0..width).map(move |col| (row, col)));
+        if part == 1 {
+            let neighbours = |(row, col): (usize, usize)| {
+                NEIGHBOURS
+                    .iter()
+                    .filter_map(move |(dr, dc)| Some((row.checked_add_signed(*dr)?, col.checked_add_signed(*dc)?)))
+                    .filter(|(r, c)| *r < height && *c < width)
+            };
+            let risk: i64 = cells
+                .filter(|&(row, col)| neighbours((row, col)).all(|(r, c)| map.rows[r][c] > map.rows[row][col]))
+                .map(|(row, col)| i64::from(map.rows[row][col]) + 1)
+                .sum();
+            return Answer::from(risk);
+        }
+        let mut sizes: Vec<i64> = Vec::new();
+        let mut seen: Vec<Vec<bool>> = vec![vec![false; width]; height];
+        for (row, col) in cells {
+            if map.rows[row][col] != 9 && !seen[row][col] {
+                let basin = spread(width, height, &[(row, col)], |_, (r, c)| map.rows[r][c] != 9);
+                let cells: Vec<(usize, usize)> = (0..height)
+                    .flat_map(|r| (0..width).map(move |c| (r, c)))
+                    .filter(|(r, c)| basin[*r][*c].is_some())
+                    .collect();
+                cells.iter().for_each(|(r, c)| seen[*r][*c] = true);
+                sizes.push(cells.len() as i64);
+            }
+        }
+        sizes.sort();
+        Answer::from(sizes.iter().rev().take(3).product::<i64>())
+    }
+
+    #[test]
+    fn matches_reference() {
+        differential::check::<Day9>(1000, 12, reference)
+    }
+}
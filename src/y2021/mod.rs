@@ -0,0 +1,26 @@
+//! Advent of Code 2021.
+
+pub static YEAR: u32 = 2021;
+
+days!(
+    day1::Day1,
+    day2::Day2,
+    day3::Day3,
+    day4::Day4,
+    day5::Day5,
+    day6::Day6,
+    day7::Day7,
+    day8::Day8,
+    day9::Day9,
+    day10::Day10,
+    day11::Day11,
+    day12::Day12,
+    day13::Day13,
+    day14::Day14,
+    day15::Day15,
+);
+
+// Work in progress, only the packet parsing exists so far, so it is not
+// registered.
+#[allow(dead_code)]
+pub mod day16;